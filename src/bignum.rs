@@ -0,0 +1,441 @@
+//! Arbitrary-precision signed integers.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+const DECIMAL_CHUNK: u32 = 1_000_000_000;
+const DECIMAL_CHUNK_DIGITS: usize = 9;
+
+/// A signed integer of unbounded size.
+///
+/// The magnitude is stored as little-endian base 2^32 limbs without
+/// trailing zero limbs, so zero has no limbs and is never negative.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+    negative: bool,
+    limbs: Vec<u32>,
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        BigInt::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.limbs.first().is_some_and(|limb| limb & 1 == 1)
+    }
+
+    /// The number of bits of the magnitude; zero has none.
+    pub fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            Some(top) => self.limbs.len() as u64 * 32 - u64::from(top.leading_zeros()),
+            None => 0,
+        }
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt::from_parts(false, self.limbs.clone())
+    }
+
+    /// Returns `10^exp`.
+    pub fn pow10(exp: u32) -> BigInt {
+        let mut limbs = vec![1];
+        for _ in 0..exp {
+            limbs = mul_small_add(&limbs, 10, 0);
+        }
+        BigInt::from_parts(false, limbs)
+    }
+
+    pub fn pow(&self, mut exp: u32) -> BigInt {
+        let mut base = self.clone();
+        let mut result = BigInt::from(1i64);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    /// Divides with the quotient truncated toward zero, so the remainder has
+    /// the sign of `self`. Returns `None` when `rhs` is zero.
+    pub fn div_rem(&self, rhs: &BigInt) -> Option<(BigInt, BigInt)> {
+        if rhs.is_zero() {
+            return None;
+        }
+        let (quotient, remainder) = div_rem_mag(&self.limbs, &rhs.limbs);
+        Some((
+            BigInt::from_parts(self.negative != rhs.negative, quotient),
+            BigInt::from_parts(self.negative, remainder),
+        ))
+    }
+
+    /// Converts to `i128` if the value fits.
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+        let magnitude = self
+            .limbs
+            .iter()
+            .rev()
+            .fold(0u128, |acc, &limb| (acc << 32) | u128::from(limb));
+        if self.negative {
+            if magnitude <= i128::MAX as u128 + 1 {
+                Some((magnitude as i128).wrapping_neg())
+            } else {
+                None
+            }
+        } else if magnitude <= i128::MAX as u128 {
+            Some(magnitude as i128)
+        } else {
+            None
+        }
+    }
+
+    /// Parses unsigned `digits` in `radix` (2 to 36), case-insensitively.
+    pub fn from_str_radix(digits: &str, radix: u32) -> Result<BigInt, String> {
+        if digits.is_empty() {
+            return Err("cannot parse integer from empty string".to_string());
+        }
+        let mut limbs = Vec::new();
+        for c in digits.chars() {
+            let digit = c
+                .to_digit(radix)
+                .ok_or_else(|| format!("invalid digit `{}` for base {}", c, radix))?;
+            limbs = mul_small_add(&limbs, radix, digit);
+        }
+        Ok(BigInt::from_parts(false, limbs))
+    }
+
+    /// The magnitude's digits in `radix` (2 to 36), lowercase, without a
+    /// sign.
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        let mut digits = Vec::new();
+        let mut limbs = self.limbs.clone();
+        while !limbs.is_empty() {
+            let (quotient, remainder) = div_rem_small(&limbs, radix);
+            digits.push(std::char::from_digit(remainder, radix).expect("digit below radix"));
+            limbs = quotient;
+        }
+        if digits.is_empty() {
+            digits.push('0');
+        }
+        digits.iter().rev().collect()
+    }
+
+    fn from_parts(negative: bool, mut limbs: Vec<u32>) -> BigInt {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        let negative = negative && !limbs.is_empty();
+        BigInt { negative, limbs }
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(value: i128) -> Self {
+        let mut magnitude = value.unsigned_abs();
+        let mut limbs = Vec::new();
+        while magnitude != 0 {
+            limbs.push(magnitude as u32);
+            magnitude >>= 32;
+        }
+        BigInt::from_parts(value < 0, limbs)
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        BigInt::from(i128::from(value))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_mag(&self.limbs, &other.limbs),
+            (true, true) => cmp_mag(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.limbs.clone())
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: &BigInt) -> BigInt {
+        if self.negative == rhs.negative {
+            return BigInt::from_parts(self.negative, add_mag(&self.limbs, &rhs.limbs));
+        }
+        match cmp_mag(&self.limbs, &rhs.limbs) {
+            Ordering::Equal => BigInt::zero(),
+            Ordering::Greater => {
+                BigInt::from_parts(self.negative, sub_mag(&self.limbs, &rhs.limbs))
+            }
+            Ordering::Less => BigInt::from_parts(rhs.negative, sub_mag(&rhs.limbs, &self.limbs)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: &BigInt) -> BigInt {
+        self + &-rhs
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: &BigInt) -> BigInt {
+        BigInt::from_parts(
+            self.negative != rhs.negative,
+            mul_mag(&self.limbs, &rhs.limbs),
+        )
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        if digits.is_empty() {
+            return Err("cannot parse integer from empty string".to_string());
+        }
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err("invalid digit found in string".to_string());
+        }
+        let mut limbs = Vec::new();
+        for chunk in digits.as_bytes().chunks(DECIMAL_CHUNK_DIGITS) {
+            let value = chunk
+                .iter()
+                .fold(0u32, |acc, &digit| acc * 10 + u32::from(digit - b'0'));
+            limbs = mul_small_add(&limbs, 10u32.pow(chunk.len() as u32), value);
+        }
+        Ok(BigInt::from_parts(negative, limbs))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut chunks = Vec::new();
+        let mut limbs = self.limbs.clone();
+        while !limbs.is_empty() {
+            let (quotient, remainder) = div_rem_small(&limbs, DECIMAL_CHUNK);
+            chunks.push(remainder);
+            limbs = quotient;
+        }
+        let mut text = String::new();
+        if self.negative {
+            text.push('-');
+        }
+        match chunks.pop() {
+            None => text.push('0'),
+            Some(first) => text.push_str(&first.to_string()),
+        }
+        for chunk in chunks.iter().rev() {
+            text.push_str(&format!("{:09}", chunk));
+        }
+        f.pad(&text)
+    }
+}
+
+fn trim(mut limbs: Vec<u32>) -> Vec<u32> {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+    limbs
+}
+
+fn cmp_mag(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut out = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (i, &limb) in long.iter().enumerate() {
+        let sum = u64::from(limb) + u64::from(short.get(i).copied().unwrap_or(0)) + carry;
+        out.push(sum as u32);
+        carry = sum >> 32;
+    }
+    out.push(carry as u32);
+    trim(out)
+}
+
+/// Computes `a - b`, requiring `a >= b`.
+fn sub_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut out = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &limb) in a.iter().enumerate() {
+        let diff = i64::from(limb) - i64::from(b.get(i).copied().unwrap_or(0)) - borrow;
+        out.push(diff as u32);
+        borrow = if diff < 0 { 1 } else { 0 };
+    }
+    trim(out)
+}
+
+fn mul_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut out = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let t = u64::from(x) * u64::from(y) + u64::from(out[i + j]) + carry;
+            out[i + j] = t as u32;
+            carry = t >> 32;
+        }
+        out[i + b.len()] = carry as u32;
+    }
+    trim(out)
+}
+
+fn mul_small_add(a: &[u32], factor: u32, addend: u32) -> Vec<u32> {
+    let mut out = Vec::with_capacity(a.len() + 1);
+    let mut carry = u64::from(addend);
+    for &limb in a {
+        let t = u64::from(limb) * u64::from(factor) + carry;
+        out.push(t as u32);
+        carry = t >> 32;
+    }
+    out.push(carry as u32);
+    trim(out)
+}
+
+fn div_rem_small(a: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder = 0u64;
+    for i in (0..a.len()).rev() {
+        let t = (remainder << 32) | u64::from(a[i]);
+        quotient[i] = (t / u64::from(divisor)) as u32;
+        remainder = t % u64::from(divisor);
+    }
+    (trim(quotient), remainder as u32)
+}
+
+fn shl_bits(a: &[u32], shift: u32) -> Vec<u32> {
+    if shift == 0 {
+        let mut out = a.to_vec();
+        out.push(0);
+        return out;
+    }
+    let mut out = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0u32;
+    for &limb in a {
+        out.push((limb << shift) | carry);
+        carry = limb >> (32 - shift);
+    }
+    out.push(carry);
+    out
+}
+
+fn shr_bits(a: &[u32], shift: u32) -> Vec<u32> {
+    if shift == 0 {
+        return trim(a.to_vec());
+    }
+    let mut out = vec![0u32; a.len()];
+    for i in 0..a.len() {
+        let high = a.get(i + 1).map_or(0, |next| next << (32 - shift));
+        out[i] = (a[i] >> shift) | high;
+    }
+    trim(out)
+}
+
+/// Long division of magnitudes (Knuth, TAOCP vol. 2, algorithm D).
+fn div_rem_mag(u: &[u32], v: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if cmp_mag(u, v) == Ordering::Less {
+        return (Vec::new(), u.to_vec());
+    }
+    if v.len() == 1 {
+        let (quotient, remainder) = div_rem_small(u, v[0]);
+        return (quotient, trim(vec![remainder]));
+    }
+
+    const BASE: u64 = 1 << 32;
+    let shift = v[v.len() - 1].leading_zeros();
+    let vn = trim(shl_bits(v, shift));
+    let mut un = shl_bits(u, shift);
+    let n = vn.len();
+    let m = u.len() - n;
+    let mut quotient = vec![0u32; m + 1];
+
+    for j in (0..=m).rev() {
+        let numerator = (u64::from(un[j + n]) << 32) | u64::from(un[j + n - 1]);
+        let mut qhat = numerator / u64::from(vn[n - 1]);
+        let mut rhat = numerator % u64::from(vn[n - 1]);
+        while qhat >= BASE
+            || qhat * u64::from(vn[n - 2]) > ((rhat << 32) | u64::from(un[j + n - 2]))
+        {
+            qhat -= 1;
+            rhat += u64::from(vn[n - 1]);
+            if rhat >= BASE {
+                break;
+            }
+        }
+
+        let mut borrow = 0i64;
+        for i in 0..n {
+            let product = qhat * u64::from(vn[i]);
+            let t = i64::from(un[i + j]) - borrow - (product & 0xffff_ffff) as i64;
+            un[i + j] = t as u32;
+            borrow = (product >> 32) as i64 - (t >> 32);
+        }
+        let t = i64::from(un[j + n]) - borrow;
+        un[j + n] = t as u32;
+
+        if t < 0 {
+            qhat -= 1;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let sum = u64::from(un[i + j]) + u64::from(vn[i]) + carry;
+                un[i + j] = sum as u32;
+                carry = sum >> 32;
+            }
+            un[j + n] = un[j + n].wrapping_add(carry as u32);
+        }
+        quotient[j] = qhat as u32;
+    }
+
+    (trim(quotient), shr_bits(&un[..n], shift))
+}
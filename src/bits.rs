@@ -0,0 +1,102 @@
+//! Bitwise operations on the fixed-width integer types.
+
+use crate::number::Number;
+
+/// An integer type with a fixed width in bits.
+pub trait Bits: Number + Copy {
+    const WIDTH: u32;
+    const SIGNED: bool;
+
+    fn and(self, rhs: Self) -> Self;
+    fn or(self, rhs: Self) -> Self;
+    fn xor(self, rhs: Self) -> Self;
+    fn not(self) -> Self;
+
+    /// Shifts left by `amount`, which must be less than `WIDTH`.
+    fn shl(self, amount: u32) -> Self;
+    /// Shifts right by `amount`, which must be less than `WIDTH`; signed
+    /// types shift in copies of the sign bit.
+    fn shr(self, amount: u32) -> Self;
+    fn rotl(self, amount: u32) -> Self;
+    fn rotr(self, amount: u32) -> Self;
+
+    fn popcount(self) -> u32;
+
+    /// The two's-complement bit pattern, zero-extended to 128 bits.
+    fn to_bits(self) -> u128;
+}
+
+macro_rules! impl_bits {
+    ($($ty:ident: $unsigned:ident, $signed:expr;)*) => {
+        $(
+            impl Bits for $ty {
+                const WIDTH: u32 = $ty::BITS;
+                const SIGNED: bool = $signed;
+
+                fn and(self, rhs: Self) -> Self {
+                    self & rhs
+                }
+
+                fn or(self, rhs: Self) -> Self {
+                    self | rhs
+                }
+
+                fn xor(self, rhs: Self) -> Self {
+                    self ^ rhs
+                }
+
+                fn not(self) -> Self {
+                    !self
+                }
+
+                fn shl(self, amount: u32) -> Self {
+                    self << amount
+                }
+
+                fn shr(self, amount: u32) -> Self {
+                    self >> amount
+                }
+
+                fn rotl(self, amount: u32) -> Self {
+                    self.rotate_left(amount)
+                }
+
+                fn rotr(self, amount: u32) -> Self {
+                    self.rotate_right(amount)
+                }
+
+                fn popcount(self) -> u32 {
+                    self.count_ones()
+                }
+
+                fn to_bits(self) -> u128 {
+                    self as $unsigned as u128
+                }
+            }
+        )*
+    };
+}
+
+impl_bits! {
+    i8: u8, true;
+    i16: u16, true;
+    i32: u32, true;
+    i64: u64, true;
+    i128: u128, true;
+    u8: u8, false;
+    u16: u16, false;
+    u32: u32, false;
+    u64: u64, false;
+    u128: u128, false;
+}
+
+/// Formats the low `width` bits of `bits` as binary, in groups of four.
+pub fn binary(bits: u128, width: u32) -> String {
+    let digits = format!("{:0>width$b}", bits, width = width as usize);
+    let groups: Vec<String> = digits
+        .as_bytes()
+        .chunks(4)
+        .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+        .collect();
+    groups.join(" ")
+}
@@ -0,0 +1,118 @@
+//! The calculator as a library API.
+
+use std::marker::PhantomData;
+
+use crate::error::CalcError;
+use crate::expr::Expression;
+use crate::number::{Number, Rounding};
+use crate::ops::{self, Operation, Overflow};
+
+/// The outcome of a successful calculation.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Calculation<T> {
+    pub value: T,
+    /// Whether the value wrapped or saturated under a non-checked overflow
+    /// mode.
+    pub overflowed: bool,
+}
+
+/// Performs calculations on values of type `T` with fixed overflow and
+/// rounding settings.
+///
+/// This is the same code the subcommands run, so results match the command
+/// line exactly.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Calculator<T> {
+    overflow: Overflow,
+    rounding: Rounding,
+    ty: PhantomData<T>,
+}
+
+impl<T: Number> Default for Calculator<T> {
+    fn default() -> Self {
+        Calculator::new()
+    }
+}
+
+impl<T: Number> Calculator<T> {
+    /// A calculator that reports overflow as an error and keeps decimal
+    /// results exact.
+    pub fn new() -> Self {
+        Calculator {
+            overflow: Overflow::Checked,
+            rounding: Rounding::default(),
+            ty: PhantomData,
+        }
+    }
+
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Parses a value the way command line operands are parsed.
+    pub fn parse(&self, text: &str) -> Result<T, CalcError> {
+        ops::parse(text)
+    }
+
+    pub fn apply(&self, op: Operation, lhs: &T, rhs: &T) -> Result<Calculation<T>, CalcError> {
+        let (value, overflowed) = ops::apply(op, lhs, rhs, self.overflow, &self.rounding)?;
+        Ok(Calculation {
+            value: value.round(&self.rounding),
+            overflowed,
+        })
+    }
+
+    /// Left-folds `op` over `operands`; returns `None` if there are none.
+    pub fn fold<'a>(
+        &self,
+        op: Operation,
+        operands: impl IntoIterator<Item = &'a T>,
+    ) -> Result<Option<Calculation<T>>, CalcError>
+    where
+        T: 'a,
+    {
+        let operands = operands.into_iter().cloned().map(Ok);
+        let folded = ops::fold(op, operands, self.overflow, &self.rounding)?;
+        Ok(folded.map(|(value, overflowed)| Calculation {
+            value: value.round(&self.rounding),
+            overflowed,
+        }))
+    }
+
+    /// Evaluates an infix expression such as `1 + 2 * (3 - 4)`.
+    pub fn eval(&self, expression: &str) -> Result<Calculation<T>, CalcError> {
+        let (value, overflowed) =
+            Expression::parse(expression)?.eval(self.overflow, &self.rounding)?;
+        Ok(Calculation { value, overflowed })
+    }
+
+    pub fn add(&self, lhs: &T, rhs: &T) -> Result<T, CalcError> {
+        self.apply(Operation::Add, lhs, rhs).map(|calc| calc.value)
+    }
+
+    pub fn sub(&self, lhs: &T, rhs: &T) -> Result<T, CalcError> {
+        self.apply(Operation::Sub, lhs, rhs).map(|calc| calc.value)
+    }
+
+    pub fn mul(&self, lhs: &T, rhs: &T) -> Result<T, CalcError> {
+        self.apply(Operation::Mul, lhs, rhs).map(|calc| calc.value)
+    }
+
+    pub fn div(&self, lhs: &T, rhs: &T) -> Result<T, CalcError> {
+        self.apply(Operation::Div, lhs, rhs).map(|calc| calc.value)
+    }
+
+    pub fn rem(&self, lhs: &T, rhs: &T) -> Result<T, CalcError> {
+        self.apply(Operation::Rem, lhs, rhs).map(|calc| calc.value)
+    }
+
+    pub fn pow(&self, lhs: &T, rhs: &T) -> Result<T, CalcError> {
+        self.apply(Operation::Pow, lhs, rhs).map(|calc| calc.value)
+    }
+}
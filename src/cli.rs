@@ -0,0 +1,263 @@
+//! The `argh` command line front-end over the calculator.
+
+use std::io::{self, IsTerminal};
+
+use argh::FromArgs;
+
+use crate::commands;
+use crate::complex::ComplexForm;
+use crate::config::Defaults;
+use crate::decimal::{RoundingMode, MAX_SCALE};
+use crate::error::CalcError;
+use crate::history::History;
+use crate::locale::Locale;
+use crate::number::{NumType, Precision, Rounding};
+use crate::output::{Format, Notation, Printer};
+use crate::radix::Radix;
+use crate::rational::FractionStyle;
+
+#[derive(FromArgs)]
+/// A simple calculation tool
+pub struct DemoCli {
+    /// numeric type of operands and results: i8, i16, i32, i64, i128,
+    /// u8, u16, u32, u64, u128, f32, f64, big, decimal, rational
+    /// (fractions such as 3/4) or complex (such as 3+4i or 5∠53.13°)
+    /// (default i64)
+    #[argh(option, long = "type")]
+    pub num_type: Option<NumType>,
+
+    /// integer precision: fixed (the width chosen by --type) or big
+    /// (arbitrary precision) (default fixed)
+    #[argh(option)]
+    pub precision: Option<Precision>,
+
+    /// use exact arbitrary-precision decimal arithmetic
+    #[argh(switch)]
+    pub decimal: bool,
+
+    /// number of fractional digits kept in decimal results, up to 100000
+    #[argh(option)]
+    pub scale: Option<u32>,
+
+    /// how decimal results are rounded to --scale: half-even, half-up or
+    /// truncate (default half-even)
+    #[argh(option)]
+    pub rounding: Option<RoundingMode>,
+
+    /// output format: plain (1 + 2 = 3), bare (just the result), json, csv
+    /// or tsv; records hold the operation, operands, result, type and
+    /// overflow flag (default plain)
+    #[argh(option)]
+    pub format: Option<Format>,
+
+    /// print integer results in this base, from 2 to 36 (default 10);
+    /// operands may use 0x, 0o and 0b prefixes and `_` separators
+    #[argh(option, default = "10")]
+    pub output_base: u32,
+
+    /// zero-pad integer results to at least this many digits
+    #[argh(option)]
+    pub pad: Option<usize>,
+
+    /// separate the digits of integer results with `_` every this many
+    /// digits, counted from the right
+    #[argh(option)]
+    pub group: Option<usize>,
+
+    /// print rational results as decimals with this many fractional digits,
+    /// up to 100000, rounded with --rounding
+    #[argh(option)]
+    pub as_decimal: Option<u32>,
+
+    /// print rational results as mixed numbers, such as 1 3/4
+    #[argh(switch)]
+    pub mixed: bool,
+
+    /// how complex results are printed: rectangular (3+4i) or polar
+    /// (5∠53.13°) (default rectangular)
+    #[argh(option, default = "ComplexForm::default()")]
+    pub complex_form: ComplexForm,
+
+    /// write numbers in plain output with the separators of this locale,
+    /// such as de_DE (1.234,5) or en_US (1,234.5); the default, C, writes
+    /// them as operands are written (1234.5)
+    #[argh(option)]
+    pub locale: Option<Locale>,
+
+    /// do not keep the calculations of this run in the history
+    #[argh(switch)]
+    pub no_history: bool,
+
+    /// read defaults for --type, --precision, --scale, --rounding, --format
+    /// and --locale from this file rather than $ARGH_DEMO_CONFIG or
+    /// ~/.config/argh-demo/config.toml;
+    /// ARGH_DEMO_TYPE, ARGH_DEMO_SCALE and the like override the file, and
+    /// options override both
+    #[argh(option)]
+    pub config: Option<String>,
+
+    #[argh(subcommand)]
+    pub subcommand: Option<SubCommands>,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand)]
+pub enum SubCommands {
+    Add(commands::add::AddOptions),
+    Sub(commands::sub::SubOptions),
+    Mul(commands::mul::MulOptions),
+    Div(commands::div::DivOptions),
+    Rem(commands::rem::RemOptions),
+    Pow(commands::pow::PowOptions),
+    Eval(commands::eval::EvalOptions),
+    Repl(commands::repl::ReplOptions),
+    Run(commands::run::RunOptions),
+    Bit(commands::bit::BitOptions),
+    Inspect(commands::inspect::InspectOptions),
+    Abs(commands::abs::AbsOptions),
+    Arg(commands::arg::ArgOptions),
+    Conj(commands::conj::ConjOptions),
+    Stats(commands::stats::StatsOptions),
+    Convert(commands::convert::ConvertOptions),
+    Date(commands::date::DateOptions),
+    Completions(commands::completions::CompletionsOptions),
+    History(commands::history::HistoryOptions),
+    Store(commands::store::StoreOptions),
+    Recall(commands::recall::RecallOptions),
+}
+
+impl DemoCli {
+    /// Fills in the options not given on the command line from `defaults`.
+    /// A configured type and precision only apply when none of `--type`,
+    /// `--precision` and `--decimal` is given, so that they cannot clash.
+    pub fn with_defaults(mut self, defaults: Defaults) -> DemoCli {
+        if self.num_type.is_none() && self.precision.is_none() && !self.decimal {
+            self.num_type = defaults.num_type;
+            self.precision = defaults.precision;
+        }
+        self.scale = self.scale.or(defaults.scale);
+        self.format = self.format.or(defaults.format);
+        self.rounding = self.rounding.or(defaults.rounding);
+        self.locale = self.locale.or(defaults.locale);
+        self
+    }
+
+    /// Resolves `--type`, `--precision` and `--decimal` into one numeric type.
+    pub fn number_type(&self) -> Result<NumType, CalcError> {
+        match (
+            self.num_type,
+            self.precision.unwrap_or_default(),
+            self.decimal,
+        ) {
+            (Some(_), _, true) => Err(CalcError::Usage(
+                "--decimal cannot be combined with --type".to_string(),
+            )),
+            (None, _, true) => Ok(NumType::Decimal),
+            (Some(NumType::Big), Precision::Big, false) | (None, Precision::Big, false) => {
+                Ok(NumType::Big)
+            }
+            (Some(_), Precision::Big, false) => Err(CalcError::Usage(
+                "--precision big cannot be combined with --type".to_string(),
+            )),
+            (num_type, Precision::Fixed, false) => Ok(num_type.unwrap_or_default()),
+        }
+    }
+
+    /// Resolves `--as-decimal` and `--mixed`, which only apply to rationals.
+    pub fn fraction_style(&self, num_type: NumType) -> Result<FractionStyle, CalcError> {
+        if (self.as_decimal.is_some() || self.mixed) && num_type != NumType::Rational {
+            return Err(CalcError::Usage(
+                "--as-decimal and --mixed need --type rational".to_string(),
+            ));
+        }
+        match (self.as_decimal, self.mixed) {
+            (Some(_), true) => Err(CalcError::Usage(
+                "--as-decimal and --mixed cannot be used together".to_string(),
+            )),
+            (Some(scale), false) => Ok(FractionStyle::Decimal(
+                check_scale("--as-decimal", scale)?,
+                self.rounding.unwrap_or_default(),
+            )),
+            (None, true) => Ok(FractionStyle::Mixed),
+            (None, false) => Ok(FractionStyle::Fraction),
+        }
+    }
+}
+
+/// Checks a number of fractional digits given with `option`.
+fn check_scale(option: &str, scale: u32) -> Result<u32, CalcError> {
+    if scale <= MAX_SCALE {
+        Ok(scale)
+    } else {
+        Err(CalcError::Usage(format!(
+            "{} is {}, which is more than the {} digits allowed",
+            option, scale, MAX_SCALE
+        )))
+    }
+}
+
+/// Runs the command line `cli`, printing results to stdout. `args` are the
+/// arguments `cli` was parsed from, kept with each calculation in the
+/// history.
+pub fn run(cli: DemoCli, args: &[String]) -> Result<(), CalcError> {
+    let defaults = Defaults::load(cli.config.as_deref())?;
+    let cli = cli.with_defaults(defaults);
+    let rounding = Rounding {
+        scale: cli
+            .scale
+            .map(|scale| check_scale("--scale", scale))
+            .transpose()?,
+        mode: cli.rounding.unwrap_or_default(),
+    };
+    let num_type = cli.number_type()?;
+    let radix = Radix::new(cli.output_base, cli.pad, cli.group)?;
+    let notation = Notation {
+        radix,
+        fractions: cli.fraction_style(num_type)?,
+        complex: cli.complex_form,
+        locale: cli.locale.unwrap_or_default(),
+    };
+    let mut printer = Printer::new(cli.format.unwrap_or_default(), notation);
+    if !cli.no_history {
+        match History::open() {
+            Ok(history) => printer.keep_history(history, args.to_vec()),
+            Err(err) => eprintln!("warning: history is not saved: {}", err),
+        }
+    }
+    // Without a subcommand, a terminal user gets the REPL.
+    let subcommand = match cli.subcommand {
+        Some(subcommand) => subcommand,
+        None if io::stdin().is_terminal() => SubCommands::Repl(Default::default()),
+        None => {
+            return Err(CalcError::Usage(
+                "no subcommand given, run with --help for usage".to_string(),
+            ))
+        }
+    };
+    let result = with_number_type!(num_type, T => match subcommand {
+        SubCommands::Add(options) => commands::add::execute::<T>(options, &rounding, &mut printer),
+        SubCommands::Sub(options) => commands::sub::execute::<T>(options, &rounding, &mut printer),
+        SubCommands::Mul(options) => commands::mul::execute::<T>(options, &rounding, &mut printer),
+        SubCommands::Div(options) => commands::div::execute::<T>(options, &rounding, &mut printer),
+        SubCommands::Rem(options) => commands::rem::execute::<T>(options, &rounding, &mut printer),
+        SubCommands::Pow(options) => commands::pow::execute::<T>(options, &rounding, &mut printer),
+        SubCommands::Eval(options) => commands::eval::execute::<T>(options, &rounding, &mut printer),
+        SubCommands::Repl(options) => commands::repl::execute::<T>(options, &rounding, &mut printer),
+        SubCommands::Run(options) => commands::run::execute::<T>(options, &rounding, &mut printer),
+        SubCommands::Bit(options) => commands::bit::run(options, num_type, &mut printer),
+        SubCommands::Inspect(options) => commands::inspect::run(options, num_type, &rounding, &mut printer),
+        SubCommands::Abs(options) => commands::abs::run(options, num_type, &mut printer),
+        SubCommands::Arg(options) => commands::arg::run(options, num_type, &mut printer),
+        SubCommands::Conj(options) => commands::conj::run(options, num_type, &mut printer),
+        SubCommands::Stats(options) => commands::stats::execute(options, &mut printer),
+        SubCommands::Convert(options) => commands::convert::execute(options, &rounding, &mut printer),
+        SubCommands::Date(options) => commands::date::execute(options, &mut printer),
+        SubCommands::Completions(options) => commands::completions::execute(options),
+        SubCommands::History(options) => commands::history::execute(options, &mut printer),
+        SubCommands::Store(options) => commands::store::execute::<T>(options, &rounding, &mut printer),
+        SubCommands::Recall(options) => commands::recall::execute(options, &mut printer),
+    });
+    // Records printed before an error are kept too.
+    printer.save_history();
+    result
+}
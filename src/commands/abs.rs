@@ -0,0 +1,34 @@
+use argh::FromArgs;
+
+use super::complex_operand;
+use crate::complex::Complex;
+use crate::error::CalcError;
+use crate::number::{NumType, Number};
+use crate::output::{Printer, Record};
+use crate::radix::Operand;
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Magnitude of a complex number
+#[argh(subcommand, name = "abs")]
+pub struct AbsOptions {
+    /// the number, such as 3+4i or 5∠53.13°
+    #[argh(option)]
+    pub num: Operand,
+}
+
+pub fn run(options: AbsOptions, num_type: NumType, printer: &mut Printer) -> Result<(), CalcError> {
+    let value = complex_operand(&options.num, num_type)?;
+    // The operand is complex, but the result is a real number.
+    let operand = printer.format_value(Complex::NAME, &value.to_string())?;
+    let result = printer.format_value(f64::NAME, &value.abs().to_string())?;
+    printer.print_formatted(&Record {
+        operation: "abs",
+        symbol: "abs",
+        operands: Some(vec![operand]),
+        result,
+        ty: f64::NAME,
+        overflowed: false,
+        rounding_error: None,
+    });
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use argh::FromArgs;
+
+use super::complex_operand;
+use crate::complex::Complex;
+use crate::error::CalcError;
+use crate::number::{NumType, Number};
+use crate::output::{Printer, Record};
+use crate::radix::Operand;
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Angle of a complex number, in radians
+#[argh(subcommand, name = "arg")]
+pub struct ArgOptions {
+    /// the number, such as 3+4i or 5∠53.13°
+    #[argh(option)]
+    pub num: Operand,
+
+    /// give the angle in degrees
+    #[argh(switch)]
+    pub degrees: bool,
+}
+
+pub fn run(options: ArgOptions, num_type: NumType, printer: &mut Printer) -> Result<(), CalcError> {
+    let value = complex_operand(&options.num, num_type)?;
+    let angle = if options.degrees {
+        value.arg().to_degrees()
+    } else {
+        value.arg()
+    };
+    // The operand is complex, but the result is a real number.
+    let operand = printer.format_value(Complex::NAME, &value.to_string())?;
+    let result = printer.format_value(f64::NAME, &angle.to_string())?;
+    printer.print_formatted(&Record {
+        operation: "arg",
+        symbol: "arg",
+        operands: Some(vec![operand]),
+        result,
+        ty: f64::NAME,
+        overflowed: false,
+        rounding_error: None,
+    });
+    Ok(())
+}
@@ -0,0 +1,443 @@
+use argh::FromArgs;
+
+use crate::bits::{self, Bits};
+use crate::error::CalcError;
+use crate::number::NumType;
+use crate::output::{Format, Printer, Record};
+use crate::radix::{Operand, Radix};
+
+#[derive(FromArgs, PartialEq, Debug)]
+/// Bitwise operations on integer types
+#[argh(subcommand, name = "bit")]
+pub struct BitOptions {
+    #[argh(subcommand)]
+    pub command: BitCommand,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand)]
+pub enum BitCommand {
+    And(AndOptions),
+    Or(OrOptions),
+    Xor(XorOptions),
+    Not(NotOptions),
+    Shl(ShlOptions),
+    Shr(ShrOptions),
+    Rotl(RotlOptions),
+    Rotr(RotrOptions),
+    Popcount(PopcountOptions),
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Bitwise AND of two numbers
+#[argh(subcommand, name = "and")]
+pub struct AndOptions {
+    /// the first number
+    #[argh(option)]
+    pub num1: Operand,
+
+    /// the second number
+    #[argh(option)]
+    pub num2: Operand,
+
+    /// print the operands and result as aligned binary rows
+    #[argh(switch)]
+    pub show_bits: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Bitwise OR of two numbers
+#[argh(subcommand, name = "or")]
+pub struct OrOptions {
+    /// the first number
+    #[argh(option)]
+    pub num1: Operand,
+
+    /// the second number
+    #[argh(option)]
+    pub num2: Operand,
+
+    /// print the operands and result as aligned binary rows
+    #[argh(switch)]
+    pub show_bits: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Bitwise exclusive OR of two numbers
+#[argh(subcommand, name = "xor")]
+pub struct XorOptions {
+    /// the first number
+    #[argh(option)]
+    pub num1: Operand,
+
+    /// the second number
+    #[argh(option)]
+    pub num2: Operand,
+
+    /// print the operands and result as aligned binary rows
+    #[argh(switch)]
+    pub show_bits: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Flip every bit of a number
+#[argh(subcommand, name = "not")]
+pub struct NotOptions {
+    /// the number
+    #[argh(option)]
+    pub num: Operand,
+
+    /// print the operand and result as aligned binary rows
+    #[argh(switch)]
+    pub show_bits: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Shift a number left, filling with zeros
+#[argh(subcommand, name = "shl")]
+pub struct ShlOptions {
+    /// the number
+    #[argh(option)]
+    pub num: Operand,
+
+    /// how many bits to shift by, less than the type's width
+    #[argh(option)]
+    pub by: u32,
+
+    /// print the operand and result as aligned binary rows
+    #[argh(switch)]
+    pub show_bits: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Shift a number right; signed types keep their sign
+#[argh(subcommand, name = "shr")]
+pub struct ShrOptions {
+    /// the number
+    #[argh(option)]
+    pub num: Operand,
+
+    /// how many bits to shift by, less than the type's width
+    #[argh(option)]
+    pub by: u32,
+
+    /// print the operand and result as aligned binary rows
+    #[argh(switch)]
+    pub show_bits: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Rotate the bits of a number left
+#[argh(subcommand, name = "rotl")]
+pub struct RotlOptions {
+    /// the number
+    #[argh(option)]
+    pub num: Operand,
+
+    /// how many bits to rotate by, less than the type's width
+    #[argh(option)]
+    pub by: u32,
+
+    /// print the operand and result as aligned binary rows
+    #[argh(switch)]
+    pub show_bits: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Rotate the bits of a number right
+#[argh(subcommand, name = "rotr")]
+pub struct RotrOptions {
+    /// the number
+    #[argh(option)]
+    pub num: Operand,
+
+    /// how many bits to rotate by, less than the type's width
+    #[argh(option)]
+    pub by: u32,
+
+    /// print the operand and result as aligned binary rows
+    #[argh(switch)]
+    pub show_bits: bool,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Count the bits set in a number
+#[argh(subcommand, name = "popcount")]
+pub struct PopcountOptions {
+    /// the number
+    #[argh(option)]
+    pub num: Operand,
+
+    /// print the operand as an aligned binary row
+    #[argh(switch)]
+    pub show_bits: bool,
+}
+
+/// Runs a bit operation with the integer type `num_type`.
+pub fn run(options: BitOptions, num_type: NumType, printer: &mut Printer) -> Result<(), CalcError> {
+    match num_type {
+        NumType::I8 => execute::<i8>(options, printer),
+        NumType::I16 => execute::<i16>(options, printer),
+        NumType::I32 => execute::<i32>(options, printer),
+        NumType::I64 => execute::<i64>(options, printer),
+        NumType::I128 => execute::<i128>(options, printer),
+        NumType::U8 => execute::<u8>(options, printer),
+        NumType::U16 => execute::<u16>(options, printer),
+        NumType::U32 => execute::<u32>(options, printer),
+        NumType::U64 => execute::<u64>(options, printer),
+        NumType::U128 => execute::<u128>(options, printer),
+        _ => Err(CalcError::Usage(format!(
+            "bit operations need a fixed-width integer type, not {}",
+            num_type
+        ))),
+    }
+}
+
+pub fn execute<T: Bits>(options: BitOptions, printer: &mut Printer) -> Result<(), CalcError> {
+    match options.command {
+        BitCommand::And(o) => {
+            binary::<T>("and", "&", T::and, &o.num1, &o.num2, o.show_bits, printer)
+        }
+        BitCommand::Or(o) => binary::<T>("or", "|", T::or, &o.num1, &o.num2, o.show_bits, printer),
+        BitCommand::Xor(o) => {
+            binary::<T>("xor", "^", T::xor, &o.num1, &o.num2, o.show_bits, printer)
+        }
+        BitCommand::Not(o) => not::<T>(&o.num, o.show_bits, printer),
+        BitCommand::Shl(o) => shift::<T>("shl", "<<", T::shl, &o.num, o.by, o.show_bits, printer),
+        BitCommand::Shr(o) => shift::<T>("shr", ">>", T::shr, &o.num, o.by, o.show_bits, printer),
+        BitCommand::Rotl(o) => {
+            shift::<T>("rotl", "rotl", T::rotl, &o.num, o.by, o.show_bits, printer)
+        }
+        BitCommand::Rotr(o) => {
+            shift::<T>("rotr", "rotr", T::rotr, &o.num, o.by, o.show_bits, printer)
+        }
+        BitCommand::Popcount(o) => popcount::<T>(&o.num, o.show_bits, printer),
+    }
+}
+
+fn binary<T: Bits>(
+    name: &'static str,
+    symbol: &'static str,
+    op: fn(T, T) -> T,
+    num1: &Operand,
+    num2: &Operand,
+    show_bits: bool,
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    let lhs: T = num1.value()?;
+    let rhs: T = num2.value()?;
+    let result = op(lhs, rhs);
+    let rows = [
+        Row::bits("", lhs),
+        Row::bits(symbol, rhs),
+        Row::bits("=", result),
+    ];
+    report(
+        name,
+        symbol,
+        &[lhs, rhs],
+        None,
+        result,
+        show_bits,
+        &rows,
+        printer,
+    )
+}
+
+fn not<T: Bits>(num: &Operand, show_bits: bool, printer: &mut Printer) -> Result<(), CalcError> {
+    let value: T = num.value()?;
+    let result = value.not();
+    let rows = [Row::bits("", value), Row::bits("~", result)];
+    report(
+        "not",
+        "~",
+        &[value],
+        None,
+        result,
+        show_bits,
+        &rows,
+        printer,
+    )
+}
+
+fn shift<T: Bits>(
+    name: &'static str,
+    symbol: &'static str,
+    op: fn(T, u32) -> T,
+    num: &Operand,
+    amount: u32,
+    show_bits: bool,
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    let value: T = num.value()?;
+    if amount >= T::WIDTH {
+        return Err(CalcError::Usage(format!(
+            "cannot {} {} by {} bits, expected 0 to {}",
+            name,
+            T::NAME,
+            amount,
+            T::WIDTH - 1
+        )));
+    }
+    let result = op(value, amount);
+    let rows = [
+        Row::bits("", value),
+        Row::bits(&format!("{} {}", symbol, amount), result),
+    ];
+    report(
+        name,
+        symbol,
+        &[value],
+        Some(amount.to_string()),
+        result,
+        show_bits,
+        &rows,
+        printer,
+    )
+}
+
+fn popcount<T: Bits>(
+    num: &Operand,
+    show_bits: bool,
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    let value: T = num.value()?;
+    let count = value.popcount();
+    let record = Record {
+        operation: "popcount",
+        symbol: "popcount",
+        operands: Some(vec![display(value, printer.radix())?]),
+        result: count.to_string(),
+        ty: T::NAME,
+        overflowed: false,
+        rounding_error: None,
+    };
+    if show_bits {
+        check_plain(printer)?;
+        let rows = [
+            Row::bits("", value),
+            Row {
+                label: "popcount".to_string(),
+                value: None,
+                text: count.to_string(),
+            },
+        ];
+        print_rows(&rows, printer.radix())?;
+        printer.remember(&record);
+        return Ok(());
+    }
+    printer.print_formatted(&record);
+    Ok(())
+}
+
+/// Prints the result of an operation on `values`, plus `extra`, an operand
+/// that is a count rather than a bit pattern, such as a shift amount.
+#[allow(clippy::too_many_arguments)]
+fn report<T: Bits>(
+    name: &'static str,
+    symbol: &'static str,
+    values: &[T],
+    extra: Option<String>,
+    result: T,
+    show_bits: bool,
+    rows: &[Row<T>],
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    let radix = printer.radix();
+    let mut operands = values
+        .iter()
+        .map(|value| display(*value, radix))
+        .collect::<Result<Vec<_>, _>>()?;
+    operands.extend(extra);
+    let record = Record {
+        operation: name,
+        symbol,
+        operands: Some(operands),
+        result: display(result, radix)?,
+        ty: T::NAME,
+        overflowed: false,
+        rounding_error: None,
+    };
+    if show_bits {
+        // The rows stand in for the record, which is still kept in the history.
+        check_plain(printer)?;
+        print_rows(rows, radix)?;
+        printer.remember(&record);
+        return Ok(());
+    }
+    printer.print_formatted(&record);
+    Ok(())
+}
+
+/// Formats `value` in the output radix. Outside base 10, negative values of
+/// signed types are shown as their two's-complement bit pattern.
+fn display<T: Bits>(value: T, radix: Radix) -> Result<String, CalcError> {
+    if radix.base() == 10 {
+        radix.format(&value.to_string())
+    } else {
+        radix.format(&value.to_bits().to_string())
+    }
+}
+
+/// Like `display`, but a signed value whose sign bit is set is followed by
+/// its value in base 10, which the two's-complement pattern hides.
+fn signed_display<T: Bits>(value: T, radix: Radix) -> Result<String, CalcError> {
+    let text = display(value, radix)?;
+    let sign_bit = value.to_bits() >> (T::WIDTH - 1) & 1 == 1;
+    if T::SIGNED && sign_bit && radix.base() != 10 {
+        Ok(format!("{} ({})", text, value))
+    } else {
+        Ok(text)
+    }
+}
+
+fn check_plain(printer: &Printer) -> Result<(), CalcError> {
+    if printer.format() == Format::Plain {
+        Ok(())
+    } else {
+        Err(CalcError::Usage(
+            "--show-bits only works with --format plain".to_string(),
+        ))
+    }
+}
+
+/// One line of `--show-bits` output: a value with its bits, or just text.
+struct Row<T> {
+    label: String,
+    value: Option<T>,
+    text: String,
+}
+
+impl<T: Bits> Row<T> {
+    fn bits(label: &str, value: T) -> Self {
+        Row {
+            label: label.to_string(),
+            value: Some(value),
+            text: String::new(),
+        }
+    }
+}
+
+fn print_rows<T: Bits>(rows: &[Row<T>], radix: Radix) -> Result<(), CalcError> {
+    let label_width = rows.iter().map(|row| row.label.len()).max().unwrap_or(0);
+    let bits_width = bits::binary(0, T::WIDTH).len();
+    for row in rows {
+        let (bits, text) = match row.value {
+            Some(value) => (
+                bits::binary(value.to_bits(), T::WIDTH),
+                signed_display(value, radix)?,
+            ),
+            None => (String::new(), row.text.clone()),
+        };
+        let line = format!(
+            "{:<label_width$} {:<bits_width$}  {}",
+            row.label,
+            bits,
+            text,
+            label_width = label_width,
+            bits_width = bits_width
+        );
+        println!("{}", line.trim_end());
+    }
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use argh::FromArgs;
+
+use crate::completions::{self, Shell};
+use crate::error::CalcError;
+
+#[derive(FromArgs, PartialEq, Debug)]
+/// Print a shell completion script
+#[argh(subcommand, name = "completions")]
+pub struct CompletionsOptions {
+    /// the shell: bash, zsh, fish, elvish or powershell
+    #[argh(option)]
+    pub shell: Shell,
+}
+
+pub fn execute(options: CompletionsOptions) -> Result<(), CalcError> {
+    print!("{}", completions::generate(options.shell));
+    Ok(())
+}
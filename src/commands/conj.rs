@@ -0,0 +1,34 @@
+use argh::FromArgs;
+
+use super::complex_operand;
+use crate::complex::Complex;
+use crate::error::CalcError;
+use crate::number::{NumType, Number};
+use crate::output::{Printer, Record};
+use crate::radix::Operand;
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Complex conjugate of a number
+#[argh(subcommand, name = "conj")]
+pub struct ConjOptions {
+    /// the number, such as 3+4i or 5∠53.13°
+    #[argh(option)]
+    pub num: Operand,
+}
+
+pub fn run(
+    options: ConjOptions,
+    num_type: NumType,
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    let value = complex_operand(&options.num, num_type)?;
+    printer.print(&Record {
+        operation: "conj",
+        symbol: "conj",
+        operands: Some(vec![value.to_string()]),
+        result: value.conj().to_string(),
+        ty: Complex::NAME,
+        overflowed: false,
+        rounding_error: None,
+    })
+}
@@ -0,0 +1,50 @@
+use argh::FromArgs;
+
+use super::unit;
+use crate::error::CalcError;
+use crate::number::Rounding;
+use crate::output::{Printer, Record};
+use crate::radix::Operand;
+use crate::units::{Quantity, Unit};
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Convert a quantity to another unit of the same dimension
+#[argh(subcommand, name = "convert")]
+pub struct ConvertOptions {
+    /// the quantity, such as 5km, 2.5GiB, 3h15m or -40°C
+    #[argh(option)]
+    pub num: Operand,
+
+    /// the unit to convert to, such as mi
+    #[argh(option)]
+    pub to: String,
+}
+
+pub fn execute(
+    options: ConvertOptions,
+    rounding: &Rounding,
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    // A shared name such as `m` in --num must mean a unit of the dimension
+    // of --to.
+    let hint = Unit::dimension_of(&options.to);
+    let num = options.num.resolve()?;
+    let quantity =
+        Quantity::parse_in(num.as_str(), hint).map_err(|reason| CalcError::InvalidNumber {
+            text: num.to_string(),
+            ty: "quantity",
+            reason,
+        })?;
+    let result = quantity.convert(unit(&options.to, Some(quantity.unit.dimension))?)?;
+    let operand = printer.localize(&quantity.display(rounding));
+    printer.print_localized(&Record {
+        operation: "convert",
+        symbol: "",
+        operands: Some(vec![operand]),
+        result: result.display(rounding),
+        ty: "quantity",
+        overflowed: false,
+        rounding_error: None,
+    });
+    Ok(())
+}
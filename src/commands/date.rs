@@ -0,0 +1,293 @@
+use std::str::FromStr;
+
+use argh::FromArgs;
+
+use crate::date::{Date, Instant, Offset};
+use crate::error::CalcError;
+use crate::output::{Printer, Record};
+
+#[derive(FromArgs, PartialEq, Debug)]
+/// Calendar arithmetic on dates, with fixed UTC offsets
+#[argh(subcommand, name = "date")]
+pub struct DateOptions {
+    #[argh(subcommand)]
+    pub command: DateCommand,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand)]
+pub enum DateCommand {
+    Add(DateAddOptions),
+    Diff(DiffOptions),
+    Week(WeekOptions),
+    ToUnix(ToUnixOptions),
+    FromUnix(FromUnixOptions),
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Move a date by years, months, weeks, days and business days, in that
+/// order; any of them may be negative
+#[argh(subcommand, name = "add")]
+pub struct DateAddOptions {
+    /// years to add
+    #[argh(option, default = "0")]
+    pub years: i64,
+
+    /// months to add; a day missing from the new month becomes its last
+    #[argh(option, default = "0")]
+    pub months: i64,
+
+    /// weeks to add
+    #[argh(option, default = "0")]
+    pub weeks: i64,
+
+    /// days to add
+    #[argh(option, default = "0")]
+    pub days: i64,
+
+    /// weekdays, Monday to Friday, to add
+    #[argh(option, default = "0")]
+    pub business_days: i64,
+
+    /// the date, as YYYY-MM-DD (default today, in UTC)
+    #[argh(positional)]
+    pub date: Option<Date>,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+/// Count the time from one date to another
+#[argh(subcommand, name = "diff")]
+pub struct DiffOptions {
+    /// what to count: days, weeks, months, years or business-days (weekdays
+    /// from the first date up to the second); weeks, months and years are
+    /// whole ones (default days)
+    #[argh(option, long = "in", default = "DiffUnit::default()")]
+    pub unit: DiffUnit,
+
+    /// the first date, as YYYY-MM-DD
+    #[argh(positional)]
+    pub from: Date,
+
+    /// the second date, as YYYY-MM-DD
+    #[argh(positional)]
+    pub to: Date,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Show the ISO 8601 week of a date
+#[argh(subcommand, name = "week")]
+pub struct WeekOptions {
+    /// the date, as YYYY-MM-DD (default today, in UTC)
+    #[argh(positional)]
+    pub date: Option<Date>,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+/// Convert a time to seconds since 1970-01-01T00:00:00Z
+#[argh(subcommand, name = "to-unix")]
+pub struct ToUnixOptions {
+    /// the time, such as 2026-10-17T12:30:00+02:00; without an offset it is
+    /// UTC, and a date alone is midnight UTC
+    #[argh(positional)]
+    pub time: Instant,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Convert seconds since 1970-01-01T00:00:00Z to a time
+#[argh(subcommand, name = "from-unix")]
+pub struct FromUnixOptions {
+    /// the UTC offset to show the time at, such as +02:00 (default Z)
+    #[argh(option, default = "Offset::default()")]
+    pub offset: Offset,
+
+    /// the timestamp; use --timestamp for negative values
+    #[argh(positional)]
+    pub seconds: Option<i64>,
+
+    /// the timestamp
+    #[argh(option)]
+    pub timestamp: Option<i64>,
+}
+
+/// What `date diff` counts.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum DiffUnit {
+    #[default]
+    Days,
+    Weeks,
+    Months,
+    Years,
+    BusinessDays,
+}
+
+impl DiffUnit {
+    pub fn name(self) -> &'static str {
+        match self {
+            DiffUnit::Days => "days",
+            DiffUnit::Weeks => "weeks",
+            DiffUnit::Months => "months",
+            DiffUnit::Years => "years",
+            DiffUnit::BusinessDays => "business-days",
+        }
+    }
+
+    /// Writes `count` of this unit, such as `1 day` or `-3 business days`.
+    pub fn count(self, count: i64) -> String {
+        let name = match self {
+            DiffUnit::Days => "day",
+            DiffUnit::Weeks => "week",
+            DiffUnit::Months => "month",
+            DiffUnit::Years => "year",
+            DiffUnit::BusinessDays => "business day",
+        };
+        let plural = if count.abs() == 1 { "" } else { "s" };
+        format!("{} {}{}", count, name, plural)
+    }
+}
+
+impl FromStr for DiffUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "days" => Ok(DiffUnit::Days),
+            "weeks" => Ok(DiffUnit::Weeks),
+            "months" => Ok(DiffUnit::Months),
+            "years" => Ok(DiffUnit::Years),
+            "business-days" => Ok(DiffUnit::BusinessDays),
+            _ => Err(format!(
+                "unknown unit `{}`, expected one of: days, weeks, months, years, business-days",
+                s
+            )),
+        }
+    }
+}
+
+const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+pub fn execute(options: DateOptions, printer: &mut Printer) -> Result<(), CalcError> {
+    match options.command {
+        DateCommand::Add(o) => add(o, printer),
+        DateCommand::Diff(o) => {
+            let count = match o.unit {
+                DiffUnit::Days => o.to.days() - o.from.days(),
+                DiffUnit::Weeks => (o.to.days() - o.from.days()) / 7,
+                DiffUnit::Months => o.from.months_until(o.to),
+                DiffUnit::Years => o.from.months_until(o.to) / 12,
+                DiffUnit::BusinessDays => o.from.business_days_until(o.to),
+            };
+            printer.print_formatted(&Record {
+                operation: "date-diff",
+                symbol: "-",
+                operands: Some(vec![o.to.to_string(), o.from.to_string()]),
+                result: o.unit.count(count),
+                ty: o.unit.name(),
+                overflowed: false,
+                rounding_error: None,
+            });
+            Ok(())
+        }
+        DateCommand::Week(o) => {
+            let date = o.date.unwrap_or_else(Date::today);
+            let (year, week) = date.iso_week();
+            let weekday = date.weekday();
+            printer.print_fields(&[
+                ("date", date.to_string()),
+                ("iso_week", format!("{}-W{:02}-{}", year, week, weekday)),
+                ("year", year.to_string()),
+                ("week", week.to_string()),
+                ("weekday", WEEKDAYS[weekday as usize - 1].to_string()),
+                ("day_of_year", date.ordinal().to_string()),
+            ]);
+            Ok(())
+        }
+        DateCommand::ToUnix(o) => {
+            printer.print_fields(&[("timestamp", o.time.timestamp().to_string())]);
+            Ok(())
+        }
+        DateCommand::FromUnix(o) => {
+            let timestamp = match (o.seconds, o.timestamp) {
+                (Some(timestamp), None) | (None, Some(timestamp)) => timestamp,
+                _ => {
+                    return Err(CalcError::Usage(
+                        "give the timestamp either positionally or with --timestamp".to_string(),
+                    ))
+                }
+            };
+            let time = Instant::from_timestamp(timestamp, o.offset).ok_or_else(out_of_range)?;
+            printer.print_fields(&[("time", time.to_string())]);
+            Ok(())
+        }
+    }
+}
+
+fn add(options: DateAddOptions, printer: &mut Printer) -> Result<(), CalcError> {
+    let date = options.date.unwrap_or_else(Date::today);
+    let months = options
+        .years
+        .checked_mul(12)
+        .and_then(|months| months.checked_add(options.months));
+    let days = options
+        .weeks
+        .checked_mul(7)
+        .and_then(|days| days.checked_add(options.days));
+    // Moves beyond 10,000 years cannot land on a four-digit year, so they
+    // are refused before they can overflow.
+    let (months, days) = match (months, days) {
+        (Some(months), Some(days))
+            if months.unsigned_abs() <= 120_000
+                && days.unsigned_abs() <= 3_660_000
+                && options.business_days.unsigned_abs() <= 2_610_000 =>
+        {
+            (months, days)
+        }
+        _ => return Err(out_of_range()),
+    };
+    let result = date
+        .add_months(months)
+        .add_days(days)
+        .add_business_days(options.business_days);
+    if !(0..=9999).contains(&result.year()) {
+        return Err(out_of_range());
+    }
+    let amounts = [
+        (options.years, "year"),
+        (options.months, "month"),
+        (options.weeks, "week"),
+        (options.days, "day"),
+        (options.business_days, "business day"),
+    ];
+    let mut moved: Vec<String> = amounts
+        .iter()
+        .filter(|&&(amount, _)| amount != 0)
+        .map(|&(amount, name)| {
+            let plural = if amount.abs() == 1 { "" } else { "s" };
+            format!("{} {}{}", amount, name, plural)
+        })
+        .collect();
+    if moved.is_empty() {
+        moved.push("0 days".to_string());
+    }
+    printer.print_formatted(&Record {
+        operation: "date-add",
+        symbol: "+",
+        operands: Some(vec![date.to_string(), moved.join(" ")]),
+        result: result.to_string(),
+        ty: "date",
+        overflowed: false,
+        rounding_error: None,
+    });
+    Ok(())
+}
+
+fn out_of_range() -> CalcError {
+    CalcError::Usage("the date is out of range, years 0000 to 9999".to_string())
+}
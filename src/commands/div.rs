@@ -0,0 +1,77 @@
+use argh::FromArgs;
+
+use crate::decimal::Decimal;
+use crate::error::CalcError;
+use crate::number::{Number, Rounding};
+use crate::ops::{self, Operation, Overflow};
+use crate::output::{Printer, Record};
+use crate::radix::Operand;
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Divide two numbers
+#[argh(subcommand, name = "div")]
+pub struct DivOptions {
+    /// the dividend.
+    #[argh(option)]
+    pub num1: Operand,
+
+    /// the divisor
+    #[argh(option)]
+    pub num2: Operand,
+
+    /// truncate the quotient toward zero, even for float and decimal types
+    #[argh(switch)]
+    pub integer: bool,
+
+    /// compute the exact quotient as a decimal, even for integer types
+    #[argh(switch, long = "true")]
+    pub true_division: bool,
+
+    /// wrap around instead of failing on overflow
+    #[argh(switch)]
+    pub wrapping: bool,
+
+    /// clamp to the type's bounds instead of failing on overflow
+    #[argh(switch)]
+    pub saturating: bool,
+}
+
+pub fn execute<T: Number>(
+    options: DivOptions,
+    rounding: &Rounding,
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    let overflow = Overflow::from_flags(options.wrapping, options.saturating)?;
+    let num1: T = options.num1.value()?;
+    let num2: T = options.num2.value()?;
+    let operands = Some(vec![num1.to_string(), num2.to_string()]);
+    match (options.integer, options.true_division) {
+        (true, true) => Err(CalcError::Usage(
+            "--integer and --true cannot be used together".to_string(),
+        )),
+        (false, true) => {
+            let lhs: Decimal = ops::parse(&num1.to_string())?;
+            let rhs: Decimal = ops::parse(&num2.to_string())?;
+            let (result, overflowed) = ops::apply(Operation::Div, &lhs, &rhs, overflow, rounding)?;
+            printer.print(&Record::new(
+                Operation::Div,
+                operands,
+                &result.round(rounding),
+                overflowed,
+            ))
+        }
+        (integer, false) => {
+            let (mut result, overflowed) =
+                ops::apply(Operation::Div, &num1, &num2, overflow, rounding)?;
+            if integer {
+                result = result.trunc();
+            }
+            printer.print(&Record::new(
+                Operation::Div,
+                operands,
+                &result.round(rounding),
+                overflowed,
+            ))
+        }
+    }
+}
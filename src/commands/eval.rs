@@ -0,0 +1,47 @@
+use argh::FromArgs;
+
+use crate::error::CalcError;
+use crate::expr::Expression;
+use crate::number::{Number, Rounding};
+use crate::ops::Overflow;
+use crate::output::{Printer, Record};
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Evaluate an infix expression
+#[argh(subcommand, name = "eval")]
+pub struct EvalOptions {
+    /// the expression, e.g. "1 + 2 * (3 - 4)"; supports + - * / % ^ and
+    /// parentheses (start it with a space if it begins with `-`)
+    #[argh(positional)]
+    pub expression: Vec<String>,
+
+    /// wrap around instead of failing on overflow
+    #[argh(switch)]
+    pub wrapping: bool,
+
+    /// clamp to the type's bounds instead of failing on overflow
+    #[argh(switch)]
+    pub saturating: bool,
+}
+
+pub fn execute<T: Number>(
+    options: EvalOptions,
+    rounding: &Rounding,
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    let overflow = Overflow::from_flags(options.wrapping, options.saturating)?;
+    if options.expression.is_empty() {
+        return Err(CalcError::Usage("no expression given".to_string()));
+    }
+    let expression = Expression::parse(options.expression.join(" ").trim())?;
+    let (result, overflowed): (T, bool) = expression.eval(overflow, rounding)?;
+    printer.print(&Record {
+        operation: "eval",
+        symbol: "",
+        operands: Some(vec![expression.source().to_string()]),
+        result: result.to_string(),
+        ty: T::NAME,
+        overflowed,
+        rounding_error: None,
+    })
+}
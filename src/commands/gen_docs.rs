@@ -0,0 +1,50 @@
+use std::fs;
+use std::path::Path;
+
+use argh::FromArgs;
+
+use crate::docs;
+use crate::error::CalcError;
+
+#[derive(FromArgs, PartialEq, Debug)]
+/// Write the man page and the Markdown command reference, and refresh the
+/// usage in the READMEs. This command is for maintainers and is left out of
+/// the help, completions and docs.
+pub struct GenDocsOptions {
+    /// the directory holding the repository's docs/ (default .)
+    #[argh(option, default = "String::from(\".\")")]
+    pub root: String,
+}
+
+pub fn execute(options: GenDocsOptions) -> Result<(), CalcError> {
+    let root = Path::new(&options.root);
+    let mut files = vec![
+        (docs::MAN_PAGE, docs::man_page()),
+        (docs::REFERENCE, docs::reference()),
+    ];
+    for file in docs::READMES {
+        let path = root.join(file);
+        let readme = fs::read_to_string(&path).map_err(|err| CalcError::Io {
+            path: path.display().to_string(),
+            action: "read",
+            message: err.to_string(),
+        })?;
+        let updated = docs::readme(&readme)
+            .ok_or_else(|| CalcError::Usage(format!("{} has no usage markers", path.display())))?;
+        files.push((file, updated));
+    }
+    for (file, contents) in files {
+        let path = root.join(file);
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&path, contents));
+        written.map_err(|err| CalcError::Io {
+            path: path.display().to_string(),
+            action: "write",
+            message: err.to_string(),
+        })?;
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use argh::FromArgs;
+
+use crate::error::CalcError;
+use crate::history::{Entry, History};
+use crate::output::{Format, Printer};
+
+#[derive(FromArgs, PartialEq, Debug)]
+/// Show, search and clear the history of calculations; `!N` in place of a
+/// number reuses the result of entry N, and `argh-demo '!N'` re-runs its
+/// command line
+#[argh(subcommand, name = "history")]
+pub struct HistoryOptions {
+    #[argh(subcommand)]
+    pub command: HistoryCommand,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand)]
+pub enum HistoryCommand {
+    List(ListOptions),
+    Search(SearchOptions),
+    Show(ShowOptions),
+    Clear(ClearOptions),
+    Export(ExportOptions),
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// List past calculations, oldest first
+#[argh(subcommand, name = "list")]
+pub struct ListOptions {
+    /// only list this many of the latest calculations
+    #[argh(option)]
+    pub last: Option<usize>,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// List past calculations whose operation, operands or result contain a
+/// text
+#[argh(subcommand, name = "search")]
+pub struct SearchOptions {
+    /// the text to look for
+    #[argh(positional)]
+    pub text: String,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Show one past calculation in full
+#[argh(subcommand, name = "show")]
+pub struct ShowOptions {
+    /// the number of the entry, as listed
+    #[argh(positional)]
+    pub id: usize,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Forget every past calculation
+#[argh(subcommand, name = "clear")]
+pub struct ClearOptions {}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Print every past calculation in full for auditing, as CSV unless
+/// --format is json or tsv
+#[argh(subcommand, name = "export")]
+pub struct ExportOptions {}
+
+pub fn execute(options: HistoryOptions, printer: &mut Printer) -> Result<(), CalcError> {
+    let history = History::open()?;
+    match options.command {
+        HistoryCommand::List(o) => {
+            let entries = history.entries()?;
+            let skip = o.last.map_or(0, |last| entries.len().saturating_sub(last));
+            list(&entries[skip..], printer);
+        }
+        HistoryCommand::Search(o) => {
+            let entries: Vec<Entry> = history
+                .entries()?
+                .into_iter()
+                .filter(|entry| entry.operation.contains(&o.text) || entry.text.contains(&o.text))
+                .collect();
+            list(&entries, printer);
+        }
+        HistoryCommand::Show(o) => printer.print_fields(&fields(&history.entry(o.id)?)),
+        HistoryCommand::Clear(_) => history.clear()?,
+        HistoryCommand::Export(_) => {
+            let format = match printer.format() {
+                Format::Plain | Format::Bare => Format::Csv,
+                format => format,
+            };
+            let mut printer = Printer::new(format, Default::default());
+            for entry in history.entries()? {
+                printer.print_fields(&fields(&entry));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Prints one line per entry in plain and bare output, and every field of
+/// each entry in the other formats.
+fn list(entries: &[Entry], printer: &mut Printer) {
+    for entry in entries {
+        match printer.format() {
+            Format::Plain => println!("{:>5}  {}  {}", entry.id, entry.time(), entry.text),
+            Format::Bare => println!("{}", entry.text),
+            _ => printer.print_fields(&fields(entry)),
+        }
+    }
+}
+
+fn fields(entry: &Entry) -> [(&'static str, String); 8] {
+    [
+        ("id", entry.id.to_string()),
+        ("time", entry.time().to_string()),
+        ("operation", entry.operation.clone()),
+        (
+            "operands",
+            entry
+                .operands
+                .as_ref()
+                .map_or_else(|| "(streamed)".to_string(), |operands| operands.join(" ")),
+        ),
+        ("result", entry.result.clone()),
+        ("type", entry.ty.clone()),
+        ("overflow", entry.overflowed.to_string()),
+        ("command", entry.command()),
+    ]
+}
@@ -0,0 +1,128 @@
+use std::num::FpCategory;
+
+use argh::FromArgs;
+
+use crate::error::CalcError;
+use crate::expr::Expression;
+use crate::float::{self, Float, Parts};
+use crate::number::{NumType, Rounding};
+use crate::ops::Overflow;
+use crate::output::Printer;
+use crate::radix;
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Show how a floating-point value is stored
+#[argh(subcommand, name = "inspect")]
+pub struct InspectOptions {
+    /// the value, or an expression such as "0.1 + 0.2"
+    #[argh(option)]
+    pub num: String,
+
+    /// another value or expression, to count the representable values
+    /// between it and --num
+    #[argh(option)]
+    pub other: Option<String>,
+}
+
+/// Runs `inspect` with the float type `num_type`.
+pub fn run(
+    options: InspectOptions,
+    num_type: NumType,
+    rounding: &Rounding,
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    match num_type {
+        NumType::F32 => execute::<f32>(options, rounding, printer),
+        NumType::F64 => execute::<f64>(options, rounding, printer),
+        _ => Err(CalcError::Usage(format!(
+            "inspect needs a float type, --type f32 or f64, not {}",
+            num_type
+        ))),
+    }
+}
+
+pub fn execute<F: Float>(
+    options: InspectOptions,
+    rounding: &Rounding,
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    let value: F = evaluate(&options.num, rounding)?;
+    let parts = Parts::of(value);
+    let category = value.category();
+    let mut fields = vec![
+        ("value", value.to_string()),
+        ("type", F::NAME.to_string()),
+        ("class", class(category).to_string()),
+        ("sign", if parts.negative { "-" } else { "+" }.to_string()),
+        ("exponent", exponent::<F>(parts, category)),
+        (
+            "mantissa",
+            format!(
+                "{:#0width$x}",
+                parts.mantissa,
+                width = (F::MANTISSA_BITS as usize).div_ceil(4) + 2
+            ),
+        ),
+        (
+            "bits",
+            format!(
+                "{} {:0ew$b} {:0mw$b}",
+                u8::from(parts.negative),
+                parts.exponent,
+                parts.mantissa,
+                ew = F::EXPONENT_BITS as usize,
+                mw = F::MANTISSA_BITS as usize
+            ),
+        ),
+        ("exact", or_undefined(float::exact(value))),
+        ("ulp", or_undefined(value.ulp())),
+    ];
+    if let Some(other) = &options.other {
+        let other: F = evaluate(other, rounding)?;
+        fields.push(("other", other.to_string()));
+        fields.push((
+            "ulp_distance",
+            or_undefined(float::ulp_distance(value, other)),
+        ));
+    }
+    printer.print_fields(&fields);
+    Ok(())
+}
+
+/// Reads a value, including `nan` and `inf`, or else evaluates an
+/// expression. Overflow and division by zero yield infinities and NaN,
+/// which are themselves worth inspecting.
+fn evaluate<F: Float>(text: &str, rounding: &Rounding) -> Result<F, CalcError> {
+    let text = text.trim();
+    if let Ok(value) = radix::parse(text) {
+        return Ok(value);
+    }
+    let (value, _) = Expression::parse(text)?.eval(Overflow::Wrapping, rounding)?;
+    Ok(value)
+}
+
+fn class(category: FpCategory) -> &'static str {
+    match category {
+        FpCategory::Nan => "nan",
+        FpCategory::Infinite => "infinite",
+        FpCategory::Zero => "zero",
+        FpCategory::Subnormal => "subnormal",
+        FpCategory::Normal => "normal",
+    }
+}
+
+/// The biased exponent field and the power of two it stands for.
+fn exponent<F: Float>(parts: Parts, category: FpCategory) -> String {
+    let power = match category {
+        FpCategory::Nan | FpCategory::Infinite => return format!("{} (all ones)", parts.exponent),
+        FpCategory::Zero => return format!("{} (zero)", parts.exponent),
+        // Subnormals share the smallest normal exponent, without the leading 1.
+        FpCategory::Subnormal => 1 - float::bias::<F>(),
+        FpCategory::Normal => parts.exponent as i64 - float::bias::<F>(),
+    };
+    format!("{} (2^{})", parts.exponent, power)
+}
+
+fn or_undefined<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(|| "undefined".to_string(), |value| value.to_string())
+}
@@ -1,2 +1,292 @@
+use crate::complex::Complex;
+use crate::error::CalcError;
+use crate::input;
+use crate::number::{NumType, Number, Rounding};
+use crate::ops::{self, Operation, Overflow};
+use crate::output::{Printer, Record};
+use crate::radix::Operand;
+use crate::rational::Rational;
+use crate::units::{Dimension, Quantity, Unit};
+
+pub mod abs;
 pub mod add;
+pub mod arg;
+pub mod bit;
+pub mod completions;
+pub mod conj;
+pub mod convert;
+pub mod date;
+pub mod div;
+pub mod eval;
+pub mod gen_docs;
+pub mod history;
+pub mod inspect;
+pub mod mul;
+pub mod pow;
+pub mod recall;
+pub mod rem;
+pub mod repl;
+pub mod run;
+pub mod stats;
+pub mod store;
 pub mod sub;
+
+/// Parses the operands given on the command line of a variadic
+/// subcommand, in order: a result recalled with `--from-history`, `--num1`,
+/// `--num2`, the positional operands, then every `--num`.
+pub fn operands<T: Number>(given: &[&Operand]) -> Result<Vec<T>, CalcError> {
+    given.iter().map(|operand| operand.value()).collect()
+}
+
+/// Looks up the references among `given`; see `Operand::resolve`.
+pub fn resolve(given: &[&Operand]) -> Result<Vec<Operand>, CalcError> {
+    given.iter().map(|operand| operand.resolve()).collect()
+}
+
+/// The result of history entry `id`, given with `--from-history`, as an
+/// operand.
+pub fn recall(id: Option<usize>) -> Result<Option<Operand>, CalcError> {
+    let result = match id {
+        Some(id) => crate::history::recall(id)?,
+        None => return Ok(None),
+    };
+    result
+        .parse()
+        .map(Some)
+        .map_err(|reason| CalcError::InvalidNumber {
+            text: result,
+            ty: "operand",
+            reason,
+        })
+}
+
+/// Parses the operand of `abs`, `arg` or `conj`, which need `--type complex`.
+pub fn complex_operand(operand: &Operand, num_type: NumType) -> Result<Complex, CalcError> {
+    if num_type != NumType::Complex {
+        return Err(CalcError::Usage(format!(
+            "this operation needs --type complex, not {}",
+            num_type
+        )));
+    }
+    operand.value()
+}
+
+/// Whether `add` or `sub` should combine quantities with units, such as
+/// `5km 300m`, rather than plain numbers.
+pub fn has_units(given: &[&Operand], to: Option<&str>) -> bool {
+    to.is_some()
+        || given
+            .iter()
+            .any(|operand| Quantity::is_quantity(operand.as_str()))
+}
+
+/// The options of `add` and `sub`, which differ only in their operation.
+pub struct FoldOptions {
+    pub num1: Option<Operand>,
+    pub num2: Option<Operand>,
+    pub num: Vec<Operand>,
+    pub wrapping: bool,
+    pub saturating: bool,
+    pub rounding_error: bool,
+    pub to: Option<String>,
+    pub human: bool,
+    pub from_history: Option<usize>,
+    pub file: Vec<String>,
+    pub stdin: bool,
+    pub operands: Vec<Operand>,
+}
+
+/// Runs `add` or `sub`: folds `op` over the operands given on the command
+/// line, in the order of `operands`, then over the numbers read from files
+/// and stdin, which are streamed rather than buffered. Operands with units
+/// are handed to `fold_quantities`.
+pub fn fold_command<T: Number>(
+    op: Operation,
+    options: FoldOptions,
+    rounding: &Rounding,
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    let overflow = Overflow::from_flags(options.wrapping, options.saturating)?;
+    if options.rounding_error {
+        printer.add_rounding_error_column();
+    }
+    let recalled = recall(options.from_history)?;
+    let given: Vec<&Operand> = recalled
+        .iter()
+        .chain(&options.num1)
+        .chain(&options.num2)
+        .chain(&options.operands)
+        .chain(&options.num)
+        .collect();
+    let resolved = resolve(&given)?;
+    let given: Vec<&Operand> = resolved.iter().collect();
+    if has_units(&given, options.to.as_deref()) {
+        let streamed = !options.file.is_empty() || options.stdin;
+        let to = options.to.as_deref();
+        return fold_quantities(op, &given, to, options.human, streamed, rounding, printer);
+    }
+    let values: Vec<T> = operands(&given)?;
+    let inputs = input::open_all(&options.file, options.stdin)?;
+    let streamed = !inputs.is_empty();
+    let stream = inputs.into_iter().flat_map(input::Input::values);
+    let track_error = options.rounding_error;
+    let mut exact = ExactFold::new(op);
+    let mut count = 0;
+    let operands = values
+        .iter()
+        .cloned()
+        .map(Ok)
+        .chain(stream)
+        .inspect(|value| {
+            count += 1;
+            if let (true, Ok(value)) = (track_error, value) {
+                exact.push(value);
+            }
+        });
+    let (result, overflowed) =
+        ops::fold(op, operands, overflow, rounding)?.ok_or_else(no_operands)?;
+    check_count(op, count)?;
+    let result = result.round(rounding);
+    // Streams can be arbitrarily long, so their operands are not reported.
+    let operands = if streamed {
+        None
+    } else {
+        Some(values.iter().map(ToString::to_string).collect())
+    };
+    let mut record = Record::new(op, operands, &result, overflowed);
+    if track_error {
+        record.rounding_error = Some(exact.error(&result));
+    }
+    printer.print(&record)
+}
+
+/// Fails unless `op` was given at least two operands; one alone would be
+/// printed as if it were negated or signed.
+fn check_count(op: Operation, count: usize) -> Result<(), CalcError> {
+    if count >= 2 {
+        Ok(())
+    } else {
+        Err(CalcError::Usage(format!(
+            "{} needs at least two numbers, got {}",
+            op.name(),
+            count
+        )))
+    }
+}
+
+/// Adds or subtracts quantities with units, converting each to the unit of
+/// the first. The result is in `to` if given, and otherwise in the unit of
+/// the first operand, or exactly in bytes or seconds for data sizes and
+/// durations. With `human`, sizes and durations are printed for reading
+/// instead.
+pub fn fold_quantities(
+    op: Operation,
+    given: &[&Operand],
+    to: Option<&str>,
+    human: bool,
+    streamed: bool,
+    rounding: &Rounding,
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    if streamed {
+        return Err(CalcError::Usage(
+            "numbers with units cannot be read from --file or --stdin".to_string(),
+        ));
+    }
+    let hint = given
+        .iter()
+        .find_map(|operand| Quantity::hint(operand.as_str()))
+        .or_else(|| to.and_then(Unit::dimension_of));
+    let quantities = given
+        .iter()
+        .map(|operand| {
+            Quantity::parse_in(operand.as_str(), hint).map_err(|reason| CalcError::InvalidNumber {
+                text: operand.to_string(),
+                ty: "quantity",
+                reason,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let (first, rest) = quantities.split_first().ok_or_else(no_operands)?;
+    check_count(op, quantities.len())?;
+    let mut result = rest
+        .iter()
+        .try_fold(first.clone(), |acc, quantity| acc.combine(op, quantity))?;
+    match to {
+        Some(to) => result = result.convert(unit(to, hint)?)?,
+        None if !human => {
+            if let Dimension::DataSize | Dimension::Time = result.unit.dimension {
+                result = result.convert(Unit::base(result.unit.dimension))?;
+            }
+        }
+        None => {}
+    }
+    let result = if human {
+        result.human(rounding)
+    } else {
+        result.display(rounding)
+    };
+    // Operands are shown as written, since `3h15m` reads better as given
+    // than as `195 min`.
+    printer.print_localized(&Record {
+        operation: op.name(),
+        symbol: op.symbol(),
+        operands: Some(given.iter().map(|operand| operand.to_string()).collect()),
+        result,
+        ty: "quantity",
+        overflowed: false,
+        rounding_error: None,
+    });
+    Ok(())
+}
+
+/// Looks up the unit named by `--to` for a quantity measuring `hint`.
+pub fn unit(name: &str, hint: Option<Dimension>) -> Result<&'static Unit, CalcError> {
+    Unit::find_in(name, hint).map_err(CalcError::Usage)
+}
+
+pub fn no_operands() -> CalcError {
+    CalcError::Usage("no operands given".to_string())
+}
+
+/// The exact result of an `add` or `sub` fold, tracked operand by operand
+/// for `--rounding-error`.
+pub struct ExactFold {
+    op: Operation,
+    value: Option<Rational>,
+    undefined: bool,
+}
+
+impl ExactFold {
+    pub fn new(op: Operation) -> Self {
+        ExactFold {
+            op,
+            value: None,
+            undefined: false,
+        }
+    }
+
+    pub fn push<T: Number>(&mut self, operand: &T) {
+        let operand = match operand.exact() {
+            Some(operand) => operand,
+            None => {
+                self.undefined = true;
+                return;
+            }
+        };
+        self.value = Some(match (self.value.take(), self.op) {
+            (None, _) => operand,
+            (Some(value), Operation::Sub) => value.sub(&operand),
+            (Some(value), _) => value.add(&operand),
+        });
+    }
+
+    /// `result` minus the exact result, as a decimal when that is exact, or
+    /// `undefined` when a NaN or an infinity is involved.
+    pub fn error<T: Number>(&self, result: &T) -> String {
+        match (&self.value, result.exact()) {
+            (Some(exact), Some(result)) if !self.undefined => result.sub(exact).to_exact_string(),
+            _ => "undefined".to_string(),
+        }
+    }
+}
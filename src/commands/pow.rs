@@ -0,0 +1,45 @@
+use argh::FromArgs;
+
+use crate::error::CalcError;
+use crate::number::{Number, Rounding};
+use crate::ops::{self, Operation, Overflow};
+use crate::output::{Printer, Record};
+use crate::radix::Operand;
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Raise a number to a power
+#[argh(subcommand, name = "pow")]
+pub struct PowOptions {
+    /// the base.
+    #[argh(option)]
+    pub num1: Operand,
+
+    /// the exponent, which must not be negative unless the type is a float
+    #[argh(option)]
+    pub num2: Operand,
+
+    /// wrap around instead of failing on overflow
+    #[argh(switch)]
+    pub wrapping: bool,
+
+    /// clamp to the type's bounds instead of failing on overflow
+    #[argh(switch)]
+    pub saturating: bool,
+}
+
+pub fn execute<T: Number>(
+    options: PowOptions,
+    rounding: &Rounding,
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    let overflow = Overflow::from_flags(options.wrapping, options.saturating)?;
+    let num1: T = options.num1.value()?;
+    let num2: T = options.num2.value()?;
+    let (result, overflowed) = ops::apply(Operation::Pow, &num1, &num2, overflow, rounding)?;
+    printer.print(&Record::new(
+        Operation::Pow,
+        Some(vec![num1.to_string(), num2.to_string()]),
+        &result.round(rounding),
+        overflowed,
+    ))
+}
@@ -0,0 +1,33 @@
+use argh::FromArgs;
+
+use crate::error::CalcError;
+use crate::output::Printer;
+use crate::registers::Registers;
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Show the numbers kept in registers with `store`
+#[argh(subcommand, name = "recall")]
+pub struct RecallOptions {
+    /// the register; without it, every register is shown
+    #[argh(positional)]
+    pub name: Option<String>,
+}
+
+pub fn execute(options: RecallOptions, printer: &mut Printer) -> Result<(), CalcError> {
+    let registers = Registers::open()?;
+    let values = match options.name {
+        Some(name) => {
+            let value = registers.get(&name)?;
+            vec![(name, value)]
+        }
+        None => registers.all()?.into_iter().collect(),
+    };
+    let fields: Vec<(&str, String)> = values
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.clone()))
+        .collect();
+    if !fields.is_empty() {
+        printer.print_fields(&fields);
+    }
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use argh::FromArgs;
+
+use crate::error::CalcError;
+use crate::number::{Number, Rounding};
+use crate::ops::{self, Operation, Overflow};
+use crate::output::{Printer, Record};
+use crate::radix::Operand;
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Remainder of dividing two numbers
+#[argh(subcommand, name = "rem")]
+pub struct RemOptions {
+    /// the dividend.
+    #[argh(option)]
+    pub num1: Operand,
+
+    /// the divisor
+    #[argh(option)]
+    pub num2: Operand,
+
+    /// use the Euclidean remainder, which is never negative, instead of
+    /// the truncated remainder, which has the sign of the dividend
+    #[argh(switch)]
+    pub euclid: bool,
+
+    /// wrap around instead of failing on overflow
+    #[argh(switch)]
+    pub wrapping: bool,
+
+    /// clamp to the type's bounds instead of failing on overflow
+    #[argh(switch)]
+    pub saturating: bool,
+}
+
+pub fn execute<T: Number>(
+    options: RemOptions,
+    rounding: &Rounding,
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    let overflow = Overflow::from_flags(options.wrapping, options.saturating)?;
+    let num1: T = options.num1.value()?;
+    let num2: T = options.num2.value()?;
+    let op = if options.euclid {
+        Operation::RemEuclid
+    } else {
+        Operation::Rem
+    };
+    let (result, overflowed) = ops::apply(op, &num1, &num2, overflow, rounding)?;
+    printer.print(&Record::new(
+        op,
+        Some(vec![num1.to_string(), num2.to_string()]),
+        &result.round(rounding),
+        overflowed,
+    ))
+}
@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use argh::FromArgs;
+
+use crate::dirs;
+use crate::error::CalcError;
+use crate::expr::{self, Expression};
+use crate::number::{Number, Rounding};
+use crate::ops::Overflow;
+use crate::output::{Format, Printer, Record};
+
+const HELP: &str = "\
+Enter an expression such as `1 + 2 * (3 - 4)` to evaluate it.
+  name = expr   assign a variable; `ans` always holds the last result
+  :help         show this message
+  :vars         list variables
+  :history      list the lines entered, in this session and earlier ones
+  :clear        forget all variables
+  :quit         leave (as does end of input)";
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Evaluate expressions interactively
+#[argh(subcommand, name = "repl")]
+pub struct ReplOptions {
+    /// wrap around instead of failing on overflow
+    #[argh(switch)]
+    pub wrapping: bool,
+
+    /// clamp to the type's bounds instead of failing on overflow
+    #[argh(switch)]
+    pub saturating: bool,
+}
+
+pub fn execute<T: Number>(
+    options: ReplOptions,
+    rounding: &Rounding,
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    let overflow = Overflow::from_flags(options.wrapping, options.saturating)?;
+    let interactive = io::stdin().is_terminal();
+    let (mut entered, mut history) = open_history();
+    let mut variables: BTreeMap<String, T> = BTreeMap::new();
+
+    if interactive {
+        println!("argh-demo ({}), type :help for help", T::NAME);
+    }
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        if interactive {
+            print!("> ");
+            io::stdout().flush().ok();
+        }
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(_)) | None => break,
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(file) = history.as_mut() {
+            writeln!(file, "{}", line).ok();
+        }
+        entered.push(line.to_string());
+        match line {
+            ":help" => println!("{}", HELP),
+            ":vars" => {
+                for (name, value) in &variables {
+                    match printer.format_value(T::NAME, &value.to_string()) {
+                        Ok(value) => println!("{} = {}", name, value),
+                        Err(err) => eprintln!("error: {}", err),
+                    }
+                }
+            }
+            ":history" => {
+                // The line just entered is `:history` itself, so it is left out.
+                for (number, line) in entered[..entered.len() - 1].iter().enumerate() {
+                    println!("{:>5}  {}", number + 1, line);
+                }
+            }
+            ":clear" => variables.clear(),
+            ":quit" | ":q" => break,
+            _ if line.starts_with(':') => eprintln!("error: unknown command `{}`", line),
+            _ => match evaluate(line, overflow, rounding, &mut variables) {
+                // Plain output stays terse in the REPL; other formats get full records.
+                Ok((target, value, overflowed)) => {
+                    let record = Record {
+                        operation: "eval",
+                        symbol: "",
+                        operands: Some(vec![line.to_string()]),
+                        result: value.to_string(),
+                        ty: T::NAME,
+                        overflowed,
+                        rounding_error: None,
+                    };
+                    let printed = match (printer.format(), target) {
+                        (Format::Plain, target) => {
+                            printer.format_value(T::NAME, &record.result).map(|value| {
+                                printer.remember(&record);
+                                match target {
+                                    Some(name) => println!("{} = {}", name, value),
+                                    None => println!("{}", value),
+                                }
+                            })
+                        }
+                        _ => printer.print(&record),
+                    };
+                    if let Err(err) = printed {
+                        eprintln!("error: {}", err);
+                    }
+                }
+                Err(err) => eprintln!("error: {}", err),
+            },
+        }
+        // A session can be long, so it is saved as it goes.
+        printer.save_history();
+    }
+    Ok(())
+}
+
+/// Evaluates one line, which is either an expression or `name = expression`,
+/// returning the assigned name, the value and whether it overflowed.
+fn evaluate<'a, T: Number>(
+    line: &'a str,
+    overflow: Overflow,
+    rounding: &Rounding,
+    variables: &mut BTreeMap<String, T>,
+) -> Result<(Option<&'a str>, T, bool), CalcError> {
+    let (target, source) = match expr::split_assignment(line) {
+        Some((name, source)) => (Some(name), source),
+        None => (None, line),
+    };
+    let (value, overflowed) =
+        Expression::parse(source)?.eval_with(overflow, rounding, variables)?;
+    variables.insert("ans".to_string(), value.clone());
+    if let Some(name) = target {
+        variables.insert(name.to_string(), value.clone());
+    }
+    Ok((target, value, overflowed))
+}
+
+/// Reads the lines entered in earlier sessions and opens the history file
+/// for appending, creating it if needed. History is best-effort: the REPL
+/// works without it.
+fn open_history() -> (Vec<String>, Option<File>) {
+    let dir = match dirs::data_dir() {
+        Some(dir) => dir,
+        None => return (Vec::new(), None),
+    };
+    let entered = match fs::read_to_string(dir.join("history")) {
+        Ok(text) => text.lines().map(str::to_string).collect(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(err) => {
+            eprintln!("warning: earlier history is not available: {}", err);
+            Vec::new()
+        }
+    };
+    let opened = fs::create_dir_all(&dir).and_then(|_| {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join("history"))
+    });
+    match opened {
+        Ok(file) => (entered, Some(file)),
+        Err(err) => {
+            eprintln!("warning: history is not saved: {}", err);
+            (entered, None)
+        }
+    }
+}
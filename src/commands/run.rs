@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+
+use argh::FromArgs;
+
+use crate::error::CalcError;
+use crate::expr::{self, Expression};
+use crate::input::Input;
+use crate::number::{Number, Rounding};
+use crate::ops::{self, Operation, Overflow};
+use crate::output::{Printer, Record};
+
+/// The operations a statement can start with, by their subcommand names.
+const OPERATIONS: [Operation; 7] = [
+    Operation::Add,
+    Operation::Sub,
+    Operation::Mul,
+    Operation::Div,
+    Operation::Rem,
+    Operation::RemEuclid,
+    Operation::Pow,
+];
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Run a script of calculations, one per line: an operation such as
+/// `add 1 2 3`, an expression such as `2 * (x + 1)`, or `name = ` either of
+/// them; `ans` holds the last result and `#` starts a comment
+#[argh(subcommand, name = "run")]
+pub struct RunOptions {
+    /// the script
+    #[argh(positional)]
+    pub script: Option<String>,
+
+    /// read the script from stdin
+    #[argh(switch)]
+    pub stdin: bool,
+
+    /// report a failing line and go on with the next instead of stopping
+    #[argh(switch)]
+    pub keep_going: bool,
+
+    /// wrap around instead of failing on overflow
+    #[argh(switch)]
+    pub wrapping: bool,
+
+    /// clamp to the type's bounds instead of failing on overflow
+    #[argh(switch)]
+    pub saturating: bool,
+}
+
+pub fn execute<T: Number>(
+    options: RunOptions,
+    rounding: &Rounding,
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    let overflow = Overflow::from_flags(options.wrapping, options.saturating)?;
+    let input = match (options.script, options.stdin) {
+        (Some(path), false) => Input::open(&path)?,
+        (None, true) => Input::stdin(),
+        _ => {
+            return Err(CalcError::Usage(
+                "give either a script or --stdin".to_string(),
+            ))
+        }
+    };
+    let source = input.name().to_string();
+    let mut variables: BTreeMap<String, T> = BTreeMap::new();
+    let mut failed: Option<(usize, CalcError)> = None;
+    for (index, line) in input.lines().enumerate() {
+        let line = line?;
+        let text = line.split('#').next().unwrap_or_default().trim();
+        if text.is_empty() {
+            continue;
+        }
+        let result = statement(text, overflow, rounding, &mut variables)
+            .and_then(|record| printer.print(&record));
+        if let Err(error) = result {
+            let error = CalcError::Statement {
+                source: source.clone(),
+                line: index + 1,
+                error: Box::new(error),
+            };
+            if !options.keep_going {
+                return Err(error);
+            }
+            eprintln!("error: {}", error);
+            failed = Some(match failed {
+                None => (1, error),
+                Some((count, first)) => (count + 1, first),
+            });
+        }
+    }
+    match failed {
+        Some((count, first)) => Err(CalcError::Failed {
+            source,
+            count,
+            first: Box::new(first),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Runs one statement, assigning its value to `ans` and to the variable it
+/// names, if any, and gives the record to print.
+fn statement<T: Number>(
+    text: &str,
+    overflow: Overflow,
+    rounding: &Rounding,
+    variables: &mut BTreeMap<String, T>,
+) -> Result<Record, CalcError> {
+    let (target, source) = match expr::split_assignment(text) {
+        Some((name, source)) => (Some(name), source),
+        None => (None, text),
+    };
+    let mut words = source.split_whitespace();
+    // A lone word such as `add` is read as a variable, not an operation.
+    let op = match (words.next(), words.clone().next()) {
+        (Some(word), Some(_)) => OPERATIONS.iter().find(|op| op.name() == word),
+        _ => None,
+    };
+    let (record, value) = match op {
+        Some(&op) => {
+            let mut overflowed = false;
+            let operands = words
+                .map(|word| {
+                    let (value, step) =
+                        Expression::parse(word)?.eval_with(overflow, rounding, variables)?;
+                    overflowed |= step;
+                    Ok(value)
+                })
+                .collect::<Result<Vec<T>, CalcError>>()?;
+            if operands.len() < 2 {
+                return Err(CalcError::Usage(format!(
+                    "`{}` needs at least two operands",
+                    op.name()
+                )));
+            }
+            let (result, step) =
+                ops::fold(op, operands.iter().cloned().map(Ok), overflow, rounding)?
+                    .expect("there are at least two operands");
+            let result = result.round(rounding);
+            let record = Record::new(
+                op,
+                Some(operands.iter().map(T::to_string).collect()),
+                &result,
+                overflowed || step,
+            );
+            (record, result)
+        }
+        None => {
+            let (value, overflowed) =
+                Expression::parse(source)?.eval_with(overflow, rounding, variables)?;
+            let record = Record {
+                operation: "eval",
+                symbol: "",
+                operands: Some(vec![text.to_string()]),
+                result: value.to_string(),
+                ty: T::NAME,
+                overflowed,
+                rounding_error: None,
+            };
+            (record, value)
+        }
+    };
+    variables.insert("ans".to_string(), value.clone());
+    if let Some(name) = target {
+        variables.insert(name.to_string(), value);
+    }
+    Ok(record)
+}
@@ -0,0 +1,511 @@
+use argh::FromArgs;
+
+use super::{no_operands, operands};
+use crate::error::CalcError;
+use crate::input;
+use crate::number::Number;
+use crate::output::{Format, Printer, Record};
+use crate::radix::Operand;
+use crate::stats::{Histogram, Mode, Moments, Quantile, EXACT_LIMIT};
+
+#[derive(FromArgs, PartialEq, Debug)]
+/// Statistics over numbers from arguments, files or stdin, computed as f64
+#[argh(subcommand, name = "stats")]
+pub struct StatsOptions {
+    #[argh(subcommand)]
+    pub command: StatsCommand,
+}
+
+#[derive(FromArgs, PartialEq, Debug)]
+#[argh(subcommand)]
+pub enum StatsCommand {
+    Mean(MeanOptions),
+    Median(MedianOptions),
+    Mode(ModeOptions),
+    Stddev(StddevOptions),
+    Variance(VarianceOptions),
+    Min(MinOptions),
+    Max(MaxOptions),
+    Percentile(PercentileOptions),
+    Histogram(HistogramOptions),
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// The arithmetic mean
+#[argh(subcommand, name = "mean")]
+pub struct MeanOptions {
+    /// a number taken after the positional ones, may be repeated; use this
+    /// form for negative values
+    #[argh(option)]
+    pub num: Vec<Operand>,
+
+    /// read more numbers from a file, or `-` for stdin; may be repeated
+    #[argh(option)]
+    pub file: Vec<String>,
+
+    /// read more numbers from stdin, after any files
+    #[argh(switch)]
+    pub stdin: bool,
+
+    /// the numbers
+    #[argh(positional)]
+    pub operands: Vec<Operand>,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// The median; estimated beyond a million values
+#[argh(subcommand, name = "median")]
+pub struct MedianOptions {
+    /// a number taken after the positional ones, may be repeated; use this
+    /// form for negative values
+    #[argh(option)]
+    pub num: Vec<Operand>,
+
+    /// read more numbers from a file, or `-` for stdin; may be repeated
+    #[argh(option)]
+    pub file: Vec<String>,
+
+    /// read more numbers from stdin, after any files
+    #[argh(switch)]
+    pub stdin: bool,
+
+    /// the numbers
+    #[argh(positional)]
+    pub operands: Vec<Operand>,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// The most frequent values, in ascending order; not kept in the history
+#[argh(subcommand, name = "mode")]
+pub struct ModeOptions {
+    /// a number taken after the positional ones, may be repeated; use this
+    /// form for negative values
+    #[argh(option)]
+    pub num: Vec<Operand>,
+
+    /// read more numbers from a file, or `-` for stdin; may be repeated
+    #[argh(option)]
+    pub file: Vec<String>,
+
+    /// read more numbers from stdin, after any files
+    #[argh(switch)]
+    pub stdin: bool,
+
+    /// the numbers
+    #[argh(positional)]
+    pub operands: Vec<Operand>,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// The sample standard deviation
+#[argh(subcommand, name = "stddev")]
+pub struct StddevOptions {
+    /// use the population standard deviation, dividing by n rather than n-1
+    #[argh(switch)]
+    pub population: bool,
+
+    /// a number taken after the positional ones, may be repeated; use this
+    /// form for negative values
+    #[argh(option)]
+    pub num: Vec<Operand>,
+
+    /// read more numbers from a file, or `-` for stdin; may be repeated
+    #[argh(option)]
+    pub file: Vec<String>,
+
+    /// read more numbers from stdin, after any files
+    #[argh(switch)]
+    pub stdin: bool,
+
+    /// the numbers
+    #[argh(positional)]
+    pub operands: Vec<Operand>,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// The sample variance
+#[argh(subcommand, name = "variance")]
+pub struct VarianceOptions {
+    /// use the population variance, dividing by n rather than n-1
+    #[argh(switch)]
+    pub population: bool,
+
+    /// a number taken after the positional ones, may be repeated; use this
+    /// form for negative values
+    #[argh(option)]
+    pub num: Vec<Operand>,
+
+    /// read more numbers from a file, or `-` for stdin; may be repeated
+    #[argh(option)]
+    pub file: Vec<String>,
+
+    /// read more numbers from stdin, after any files
+    #[argh(switch)]
+    pub stdin: bool,
+
+    /// the numbers
+    #[argh(positional)]
+    pub operands: Vec<Operand>,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// The smallest value
+#[argh(subcommand, name = "min")]
+pub struct MinOptions {
+    /// a number taken after the positional ones, may be repeated; use this
+    /// form for negative values
+    #[argh(option)]
+    pub num: Vec<Operand>,
+
+    /// read more numbers from a file, or `-` for stdin; may be repeated
+    #[argh(option)]
+    pub file: Vec<String>,
+
+    /// read more numbers from stdin, after any files
+    #[argh(switch)]
+    pub stdin: bool,
+
+    /// the numbers
+    #[argh(positional)]
+    pub operands: Vec<Operand>,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// The largest value
+#[argh(subcommand, name = "max")]
+pub struct MaxOptions {
+    /// a number taken after the positional ones, may be repeated; use this
+    /// form for negative values
+    #[argh(option)]
+    pub num: Vec<Operand>,
+
+    /// read more numbers from a file, or `-` for stdin; may be repeated
+    #[argh(option)]
+    pub file: Vec<String>,
+
+    /// read more numbers from stdin, after any files
+    #[argh(switch)]
+    pub stdin: bool,
+
+    /// the numbers
+    #[argh(positional)]
+    pub operands: Vec<Operand>,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// A percentile, interpolated between the closest values; estimated beyond
+/// a million values
+#[argh(subcommand, name = "percentile")]
+pub struct PercentileOptions {
+    /// the percentile, from 0 to 100
+    #[argh(option)]
+    pub p: f64,
+
+    /// a number taken after the positional ones, may be repeated; use this
+    /// form for negative values
+    #[argh(option)]
+    pub num: Vec<Operand>,
+
+    /// read more numbers from a file, or `-` for stdin; may be repeated
+    #[argh(option)]
+    pub file: Vec<String>,
+
+    /// read more numbers from stdin, after any files
+    #[argh(switch)]
+    pub stdin: bool,
+
+    /// the numbers
+    #[argh(positional)]
+    pub operands: Vec<Operand>,
+}
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Count values in equal-width bins; not kept in the history
+#[argh(subcommand, name = "histogram")]
+pub struct HistogramOptions {
+    /// the number of bins (default 10)
+    #[argh(option, default = "10")]
+    pub bins: usize,
+
+    /// the lower bound of the first bin; with --max, values are counted as
+    /// they are read instead of being held in memory (default the smallest
+    /// value)
+    #[argh(option)]
+    pub min: Option<f64>,
+
+    /// the upper bound of the last bin (default the largest value)
+    #[argh(option)]
+    pub max: Option<f64>,
+
+    /// a number taken after the positional ones, may be repeated; use this
+    /// form for negative values
+    #[argh(option)]
+    pub num: Vec<Operand>,
+
+    /// read more numbers from a file, or `-` for stdin; may be repeated
+    #[argh(option)]
+    pub file: Vec<String>,
+
+    /// read more numbers from stdin, after any files
+    #[argh(switch)]
+    pub stdin: bool,
+
+    /// the numbers
+    #[argh(positional)]
+    pub operands: Vec<Operand>,
+}
+
+/// The widest bar of a plain histogram.
+const BAR_WIDTH: u64 = 40;
+
+pub fn execute(options: StatsOptions, printer: &mut Printer) -> Result<(), CalcError> {
+    match options.command {
+        StatsCommand::Mean(o) => {
+            let moments = moments(values(o.operands, o.num, &o.file, o.stdin)?)?;
+            print(printer, "mean", moments.mean().unwrap_or(f64::NAN))
+        }
+        StatsCommand::Median(o) => {
+            let values = values(o.operands, o.num, &o.file, o.stdin)?;
+            quantile(printer, "median", "median", 0.5, values)
+        }
+        StatsCommand::Mode(o) => {
+            let mut mode = Mode::default();
+            consume(values(o.operands, o.num, &o.file, o.stdin)?, |value| {
+                mode.push(value)
+            })?;
+            let modes = mode
+                .modes()
+                .iter()
+                .map(|value| printer.format_value(f64::NAME, &value.to_string()))
+                .collect::<Result<Vec<_>, _>>()?;
+            printer.print_fields(&[("mode", modes.join(" "))]);
+            Ok(())
+        }
+        StatsCommand::Stddev(o) => {
+            let moments = moments(values(o.operands, o.num, &o.file, o.stdin)?)?;
+            let variance = variance(&moments, o.population)?;
+            print(printer, "stddev", variance.sqrt())
+        }
+        StatsCommand::Variance(o) => {
+            let moments = moments(values(o.operands, o.num, &o.file, o.stdin)?)?;
+            let variance = variance(&moments, o.population)?;
+            print(printer, "variance", variance)
+        }
+        StatsCommand::Min(o) => {
+            let moments = moments(values(o.operands, o.num, &o.file, o.stdin)?)?;
+            print(printer, "min", moments.min().unwrap_or(f64::NAN))
+        }
+        StatsCommand::Max(o) => {
+            let moments = moments(values(o.operands, o.num, &o.file, o.stdin)?)?;
+            print(printer, "max", moments.max().unwrap_or(f64::NAN))
+        }
+        StatsCommand::Percentile(o) => {
+            if !(0.0..=100.0).contains(&o.p) {
+                return Err(CalcError::Usage(format!(
+                    "--p must be from 0 to 100, not {}",
+                    o.p
+                )));
+            }
+            let values = values(o.operands, o.num, &o.file, o.stdin)?;
+            quantile(
+                printer,
+                "percentile",
+                &format!("p{}", o.p),
+                o.p / 100.0,
+                values,
+            )
+        }
+        StatsCommand::Histogram(o) => histogram(o, printer),
+    }
+}
+
+/// The numbers given on the command line, then those read from `file` and
+/// stdin.
+fn values(
+    positional: Vec<Operand>,
+    num: Vec<Operand>,
+    file: &[String],
+    stdin: bool,
+) -> Result<impl Iterator<Item = Result<f64, CalcError>>, CalcError> {
+    let given: Vec<&Operand> = positional.iter().chain(&num).collect();
+    let values: Vec<f64> = operands(&given)?;
+    let inputs = input::open_all(file, stdin)?;
+    Ok(values
+        .into_iter()
+        .map(Ok)
+        .chain(inputs.into_iter().flat_map(input::Input::values)))
+}
+
+/// Feeds every value to `push`, failing if there are none.
+fn consume(
+    values: impl Iterator<Item = Result<f64, CalcError>>,
+    mut push: impl FnMut(f64),
+) -> Result<(), CalcError> {
+    let mut empty = true;
+    for value in values {
+        push(value?);
+        empty = false;
+    }
+    if empty {
+        Err(no_operands())
+    } else {
+        Ok(())
+    }
+}
+
+fn moments(values: impl Iterator<Item = Result<f64, CalcError>>) -> Result<Moments, CalcError> {
+    let mut moments = Moments::default();
+    consume(values, |value| moments.push(value))?;
+    Ok(moments)
+}
+
+fn variance(moments: &Moments, population: bool) -> Result<f64, CalcError> {
+    moments.variance(population).ok_or_else(|| {
+        CalcError::Usage(
+            "the sample variance needs at least two values, or use --population".to_string(),
+        )
+    })
+}
+
+fn quantile(
+    printer: &mut Printer,
+    operation: &'static str,
+    name: &str,
+    p: f64,
+    values: impl Iterator<Item = Result<f64, CalcError>>,
+) -> Result<(), CalcError> {
+    let mut quantile = Quantile::new(p);
+    consume(values, |value| quantile.push(value))?;
+    let (value, estimated) = quantile.value().ok_or_else(no_operands)?;
+    if estimated {
+        eprintln!(
+            "warning: {} is estimated, as there are more than {} values",
+            name, EXACT_LIMIT
+        );
+    }
+    print_as(printer, operation, name, value)
+}
+
+fn print(printer: &mut Printer, name: &'static str, value: f64) -> Result<(), CalcError> {
+    print_as(printer, name, name, value)
+}
+
+/// Prints `value` labelled `name`, and keeps it in the history as the
+/// result of `operation`. The operands are not kept, as they may be
+/// streamed. The several values of `mode` and the bins of `histogram` are
+/// not results that could be reused, so they are not kept.
+fn print_as(
+    printer: &mut Printer,
+    operation: &'static str,
+    name: &str,
+    value: f64,
+) -> Result<(), CalcError> {
+    let shown = printer.format_value(f64::NAME, &value.to_string())?;
+    printer.remember(&Record {
+        operation,
+        symbol: "",
+        operands: None,
+        result: value.to_string(),
+        ty: f64::NAME,
+        overflowed: false,
+        rounding_error: None,
+    });
+    printer.print_fields(&[(name, shown)]);
+    Ok(())
+}
+
+fn histogram(options: HistogramOptions, printer: &mut Printer) -> Result<(), CalcError> {
+    if options.bins == 0 {
+        return Err(CalcError::Usage("--bins must be at least 1".to_string()));
+    }
+    let values = values(options.operands, options.num, &options.file, options.stdin)?;
+    let histogram = match (options.min, options.max) {
+        (Some(min), Some(max)) => {
+            check_range(min, max)?;
+            let mut histogram = Histogram::new(min, max, options.bins);
+            consume(values, |value| histogram.push(value))?;
+            histogram
+        }
+        // The range comes from the values, so they are read before counting.
+        (min, max) => {
+            let mut seen = Vec::new();
+            consume(values, |value| seen.push(value))?;
+            let finite = || seen.iter().copied().filter(|value| value.is_finite());
+            let min = min.or_else(|| finite().reduce(f64::min)).unwrap_or(0.0);
+            let max = max.or_else(|| finite().reduce(f64::max)).unwrap_or(0.0);
+            check_range(min, max)?;
+            let mut histogram = Histogram::new(min, max, options.bins);
+            seen.into_iter().for_each(|value| histogram.push(value));
+            histogram
+        }
+    };
+    if histogram.outside() > 0 {
+        let (min, max) = histogram.range();
+        eprintln!(
+            "warning: {} values outside [{}, {}] were not counted",
+            histogram.outside(),
+            min,
+            max
+        );
+    }
+    let bins = histogram
+        .bins()
+        .into_iter()
+        .map(|(lower, upper, count)| {
+            Ok((
+                printer.format_value(f64::NAME, &lower.to_string())?,
+                printer.format_value(f64::NAME, &upper.to_string())?,
+                count,
+            ))
+        })
+        .collect::<Result<Vec<_>, CalcError>>()?;
+    match printer.format() {
+        Format::Plain => {
+            let last = bins.len() - 1;
+            let labels: Vec<String> = bins
+                .iter()
+                .enumerate()
+                .map(|(i, (lower, upper, _))| {
+                    let close = if i == last { ']' } else { ')' };
+                    format!("[{}, {}{}", lower, upper, close)
+                })
+                .collect();
+            let label_width = labels.iter().map(String::len).max().unwrap_or(0);
+            let top = bins.iter().map(|&(_, _, count)| count).max().unwrap_or(0);
+            let count_width = top.to_string().len();
+            for (label, (_, _, count)) in labels.iter().zip(&bins) {
+                let bar = (count * BAR_WIDTH).checked_div(top).unwrap_or(0);
+                println!(
+                    "{:<label_width$}  {:>count_width$}  {}",
+                    label,
+                    count,
+                    "#".repeat(bar as usize),
+                    label_width = label_width,
+                    count_width = count_width
+                );
+            }
+        }
+        Format::Bare => bins.iter().for_each(|(_, _, count)| println!("{}", count)),
+        _ => {
+            for (lower, upper, count) in bins {
+                printer.print_fields(&[
+                    ("lower", lower),
+                    ("upper", upper),
+                    ("count", count.to_string()),
+                ]);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_range(min: f64, max: f64) -> Result<(), CalcError> {
+    if min <= max {
+        Ok(())
+    } else {
+        Err(CalcError::Usage(format!(
+            "--min {} is greater than --max {}",
+            min, max
+        )))
+    }
+}
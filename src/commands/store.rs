@@ -0,0 +1,103 @@
+use argh::FromArgs;
+
+use crate::error::CalcError;
+use crate::number::{Number, Rounding};
+use crate::ops::{self, Operation, Overflow};
+use crate::output::Printer;
+use crate::radix::Operand;
+use crate::registers::Registers;
+use crate::units::Quantity;
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Keep a number in a named register, where later commands can use it as
+/// @NAME in place of a number
+#[argh(subcommand, name = "store")]
+pub struct StoreOptions {
+    /// the register, such as total
+    #[argh(positional)]
+    pub name: String,
+
+    /// the number to keep; use --num for negative values
+    #[argh(positional)]
+    pub value: Option<Operand>,
+
+    /// the number to keep
+    #[argh(option)]
+    pub num: Option<Operand>,
+
+    /// add the number to the register instead of replacing it, like M+;
+    /// an empty register counts as 0
+    #[argh(switch)]
+    pub add: bool,
+
+    /// subtract the number from the register instead of replacing it, like
+    /// M-; an empty register counts as 0
+    #[argh(switch)]
+    pub sub: bool,
+}
+
+pub fn execute<T: Number>(
+    options: StoreOptions,
+    rounding: &Rounding,
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    let operand = match (options.value, options.num) {
+        (Some(operand), None) | (None, Some(operand)) => operand,
+        _ => {
+            return Err(CalcError::Usage(
+                "give the number either positionally or with --num".to_string(),
+            ))
+        }
+    };
+    let op = match (options.add, options.sub) {
+        (false, false) => None,
+        (true, false) => Some(Operation::Add),
+        (false, true) => Some(Operation::Sub),
+        (true, true) => {
+            return Err(CalcError::Usage(
+                "--add and --sub cannot be used together".to_string(),
+            ))
+        }
+    };
+    let operand = operand.resolve()?;
+    let text = operand.as_str();
+    let quantity = Quantity::is_quantity(text);
+    if quantity && op.is_some() {
+        return Err(CalcError::Usage(
+            "--add and --sub work on plain numbers, not numbers with units".to_string(),
+        ));
+    }
+    // Values are checked before they are kept, so that every register can
+    // be used as an operand.
+    let value: Option<T> = if quantity {
+        text.parse::<Quantity>()
+            .map_err(|reason| CalcError::InvalidNumber {
+                text: text.to_string(),
+                ty: "quantity",
+                reason,
+            })?;
+        None
+    } else {
+        Some(ops::parse(text)?)
+    };
+    let registers = Registers::open()?;
+    let stored = registers.update(&options.name, |current| match (op, value) {
+        (Some(op), Some(value)) => {
+            let current: T = match current {
+                Some(current) => ops::parse(current)?,
+                None => T::zero(),
+            };
+            let (result, _) = ops::apply(op, &current, &value, Overflow::Checked, rounding)?;
+            Ok(result.round(rounding).to_string())
+        }
+        (None, Some(value)) => Ok(value.to_string()),
+        _ => Ok(text.to_string()),
+    })?;
+    let shown = if quantity {
+        stored
+    } else {
+        printer.format_value(T::NAME, &stored)?
+    };
+    printer.print_fields(&[(options.name.as_str(), shown)]);
+    Ok(())
+}
@@ -1,23 +1,92 @@
 use argh::FromArgs;
 
-#[derive(FromArgs, PartialEq, Debug)]
-/// Sub two numbers
+use super::{fold_command, FoldOptions};
+use crate::error::CalcError;
+use crate::number::{Number, Rounding};
+use crate::ops::Operation;
+use crate::output::Printer;
+use crate::radix::Operand;
+
+#[derive(FromArgs, PartialEq, Debug, Default)]
+/// Subtract the other numbers from the first, in order
 #[argh(subcommand, name = "sub")]
 pub struct SubOptions {
     /// the first number.
     #[argh(option)]
-    num1: i16,
+    pub num1: Option<Operand>,
 
     /// the second number
     #[argh(option)]
-    num2: i16,
+    pub num2: Option<Operand>,
+
+    /// a number taken after the positional ones, may be repeated; use this
+    /// form for negative values
+    #[argh(option)]
+    pub num: Vec<Operand>,
+
+    /// wrap around instead of failing on overflow
+    #[argh(switch)]
+    pub wrapping: bool,
+
+    /// clamp to the type's bounds instead of failing on overflow
+    #[argh(switch)]
+    pub saturating: bool,
+
+    /// also report the result minus the exact result, such as the error a
+    /// float sum picks up from rounding
+    #[argh(switch)]
+    pub rounding_error: bool,
+
+    /// print the result in this unit, such as mi; operands with units,
+    /// such as 5km, are converted to the unit of the first
+    #[argh(option)]
+    pub to: Option<String>,
+
+    /// print data sizes and durations for reading, such as 1.85 GiB or
+    /// 4h 2m, rather than exactly in bytes or seconds
+    #[argh(switch)]
+    pub human: bool,
+
+    /// start from the result of this history entry, before --num1
+    #[argh(option)]
+    pub from_history: Option<usize>,
+
+    /// read more numbers from a file, or `-` for stdin; may be repeated
+    #[argh(option)]
+    pub file: Vec<String>,
+
+    /// read more numbers from stdin, after any files
+    #[argh(switch)]
+    pub stdin: bool,
+
+    /// more numbers, after --num1 and --num2
+    #[argh(positional)]
+    pub operands: Vec<Operand>,
+}
+
+pub fn execute<T: Number>(
+    options: SubOptions,
+    rounding: &Rounding,
+    printer: &mut Printer,
+) -> Result<(), CalcError> {
+    fold_command::<T>(Operation::Sub, options.into(), rounding, printer)
 }
 
-pub fn execute(options: SubOptions) {
-    println!(
-        "{} - {} = {}",
-        options.num1,
-        options.num2,
-        options.num1 - options.num2
-    );
+impl From<SubOptions> for FoldOptions {
+    fn from(options: SubOptions) -> Self {
+        FoldOptions {
+            num1: options.num1,
+            num2: options.num2,
+            num: options.num,
+            wrapping: options.wrapping,
+            saturating: options.saturating,
+            rounding_error: options.rounding_error,
+            to: options.to,
+            human: options.human,
+            from_history: options.from_history,
+            file: options.file,
+            stdin: options.stdin,
+            operands: options.operands,
+        }
+    }
 }
@@ -0,0 +1,366 @@
+//! Shell completion scripts for the command line, generated from the
+//! commands in `help::command_tree`.
+
+use std::fmt::Write;
+use std::str::FromStr;
+
+use crate::help::{self, Command, PROGRAM};
+
+/// A shell that completion scripts can be generated for.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    Elvish,
+    PowerShell,
+}
+
+impl Shell {
+    pub const ALL: [Shell; 5] = [
+        Shell::Bash,
+        Shell::Zsh,
+        Shell::Fish,
+        Shell::Elvish,
+        Shell::PowerShell,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+            Shell::Elvish => "elvish",
+            Shell::PowerShell => "powershell",
+        }
+    }
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Shell::ALL
+            .iter()
+            .copied()
+            .find(|shell| shell.name() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown shell `{}`, expected one of: bash, zsh, fish, elvish, powershell",
+                    s
+                )
+            })
+    }
+}
+
+/// The completion script for `shell`.
+pub fn generate(shell: Shell) -> String {
+    let tree = help::command_tree();
+    match shell {
+        Shell::Bash => bash(&tree),
+        Shell::Zsh => zsh(&tree),
+        Shell::Fish => fish(&tree),
+        Shell::Elvish => elvish(&tree),
+        Shell::PowerShell => powershell(&tree),
+    }
+}
+
+/// Every command key except the program's, as alternatives of a shell
+/// `case` pattern.
+fn keys(tree: &Command, quote: fn(&str) -> String, separator: &str) -> String {
+    tree.walk()[1..]
+        .iter()
+        .map(|command| quote(&command.key()))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn words(command: &Command) -> Vec<&str> {
+    command
+        .options
+        .iter()
+        .map(|option| option.long.as_str())
+        .chain(command.subcommands.iter().map(Command::name))
+        .collect()
+}
+
+fn value_options(command: &Command) -> Vec<&str> {
+    command
+        .options
+        .iter()
+        .filter(|option| option.takes_value)
+        .map(|option| option.long.as_str())
+        .collect()
+}
+
+/// Quotes for POSIX shells and zsh.
+fn single_quoted(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+/// Quotes for elvish and PowerShell, which double single quotes.
+fn doubled_quotes(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+fn bash(tree: &Command) -> String {
+    let mut out = String::new();
+    let function = format!("_{}", PROGRAM.replace('-', "_"));
+    writeln!(out, "{}() {{", function).unwrap();
+    out.push_str("    local cur prev word cmd opts values i\n");
+    out.push_str("    cur=\"${COMP_WORDS[COMP_CWORD]}\"\n");
+    out.push_str("    prev=\"${COMP_WORDS[COMP_CWORD-1]}\"\n");
+    writeln!(out, "    cmd={}", single_quoted(PROGRAM)).unwrap();
+    out.push_str("    for ((i = 1; i < COMP_CWORD; i++)); do\n");
+    out.push_str("        word=\"${COMP_WORDS[i]}\"\n");
+    out.push_str("        case \"${cmd};${word}\" in\n");
+    writeln!(out, "            {})", keys(tree, single_quoted, "|")).unwrap();
+    out.push_str("                cmd=\"${cmd};${word}\"\n");
+    out.push_str("                ;;\n");
+    out.push_str("        esac\n");
+    out.push_str("    done\n");
+    out.push_str("    case \"${cmd}\" in\n");
+    for command in tree.walk() {
+        writeln!(out, "        {})", single_quoted(&command.key())).unwrap();
+        writeln!(
+            out,
+            "            opts={}",
+            single_quoted(&words(command).join(" "))
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "            values={}",
+            single_quoted(&value_options(command).join(" "))
+        )
+        .unwrap();
+        out.push_str("            ;;\n");
+    }
+    out.push_str("    esac\n");
+    out.push_str("    if [[ -n \"${values}\" && \" ${values} \" == *\" ${prev} \"* ]]; then\n");
+    out.push_str("        COMPREPLY=($(compgen -f -- \"${cur}\"))\n");
+    out.push_str("        return 0\n");
+    out.push_str("    fi\n");
+    out.push_str("    COMPREPLY=($(compgen -W \"${opts}\" -- \"${cur}\"))\n");
+    out.push_str("}\n\n");
+    writeln!(
+        out,
+        "complete -F {} -o bashdefault -o default {}",
+        function, PROGRAM
+    )
+    .unwrap();
+    out
+}
+
+fn zsh(tree: &Command) -> String {
+    // `_describe` splits entries at the first unescaped colon.
+    let entry = |name: &str, description: &str| {
+        single_quoted(&format!("{}:{}", name.replace(':', "\\:"), description))
+    };
+    let mut out = String::new();
+    let function = format!("_{}", PROGRAM.replace('-', "_"));
+    writeln!(out, "#compdef {}\n", PROGRAM).unwrap();
+    writeln!(out, "{}() {{", function).unwrap();
+    out.push_str("    local cmd word i\n");
+    out.push_str("    local -a options commands values\n");
+    writeln!(out, "    cmd={}", single_quoted(PROGRAM)).unwrap();
+    out.push_str("    for ((i = 2; i < CURRENT; i++)); do\n");
+    out.push_str("        word=\"${words[i]}\"\n");
+    out.push_str("        case \"${cmd};${word}\" in\n");
+    writeln!(out, "            {})", keys(tree, single_quoted, "|")).unwrap();
+    out.push_str("                cmd=\"${cmd};${word}\"\n");
+    out.push_str("                ;;\n");
+    out.push_str("        esac\n");
+    out.push_str("    done\n");
+    out.push_str("    case \"${cmd}\" in\n");
+    for command in tree.walk() {
+        writeln!(out, "        {})", single_quoted(&command.key())).unwrap();
+        out.push_str("            options=(\n");
+        for option in &command.options {
+            writeln!(
+                out,
+                "                {}",
+                entry(&option.long, &option.description)
+            )
+            .unwrap();
+        }
+        out.push_str("            )\n");
+        out.push_str("            commands=(\n");
+        for subcommand in &command.subcommands {
+            writeln!(
+                out,
+                "                {}",
+                entry(subcommand.name(), &subcommand.description)
+            )
+            .unwrap();
+        }
+        out.push_str("            )\n");
+        let values: Vec<String> = value_options(command)
+            .into_iter()
+            .map(single_quoted)
+            .collect();
+        writeln!(out, "            values=({})", values.join(" ")).unwrap();
+        out.push_str("            ;;\n");
+    }
+    out.push_str("    esac\n");
+    out.push_str("    if (( ${values[(Ie)${words[CURRENT-1]}]} )); then\n");
+    out.push_str("        _files\n");
+    out.push_str("        return\n");
+    out.push_str("    fi\n");
+    out.push_str("    _describe -t options 'option' options\n");
+    out.push_str("    _describe -t commands 'command' commands\n");
+    out.push_str("}\n\n");
+    writeln!(out, "{} \"$@\"", function).unwrap();
+    out
+}
+
+fn fish(tree: &Command) -> String {
+    let mut out = String::new();
+    let function = format!("__{}_path", PROGRAM.replace('-', "_"));
+    writeln!(out, "function {}", function).unwrap();
+    writeln!(out, "    set -l cmd {}", single_quoted(PROGRAM)).unwrap();
+    out.push_str("    for word in (commandline -opc)[2..-1]\n");
+    out.push_str("        switch \"$cmd;$word\"\n");
+    writeln!(out, "            case {}", keys(tree, single_quoted, " ")).unwrap();
+    out.push_str("                set cmd \"$cmd;$word\"\n");
+    out.push_str("        end\n");
+    out.push_str("    end\n");
+    out.push_str("    echo $cmd\n");
+    out.push_str("end\n\n");
+    writeln!(out, "complete -c {} -f", PROGRAM).unwrap();
+    for command in tree.walk() {
+        // Keys have no quotes or `$`, so double quotes can hold them.
+        let condition = single_quoted(&format!("test ({}) = \"{}\"", function, command.key()));
+        for option in &command.options {
+            let name = option.long.trim_start_matches('-');
+            let value = if option.takes_value { " -r -F" } else { "" };
+            writeln!(
+                out,
+                "complete -c {} -n {} -l {}{} -d {}",
+                PROGRAM,
+                condition,
+                name,
+                value,
+                single_quoted(&option.description)
+            )
+            .unwrap();
+        }
+        for subcommand in &command.subcommands {
+            writeln!(
+                out,
+                "complete -c {} -n {} -a {} -d {}",
+                PROGRAM,
+                condition,
+                subcommand.name(),
+                single_quoted(&subcommand.description)
+            )
+            .unwrap();
+        }
+    }
+    out
+}
+
+fn elvish(tree: &Command) -> String {
+    let mut out = String::new();
+    out.push_str("use builtin;\nuse str;\n\n");
+    writeln!(
+        out,
+        "set edit:completion:arg-completer[{}] = {{|@words|",
+        PROGRAM
+    )
+    .unwrap();
+    out.push_str("    fn spaces {|n|\n");
+    out.push_str("        builtin:repeat $n ' ' | str:join ''\n");
+    out.push_str("    }\n");
+    out.push_str("    fn cand {|text desc|\n");
+    out.push_str(
+        "        edit:complex-candidate $text &display=$text' '(spaces (- 18 (wcswidth $text)))$desc\n",
+    );
+    out.push_str("    }\n");
+    out.push_str("    var completions = [\n");
+    for command in tree.walk() {
+        writeln!(out, "        &{}= {{", doubled_quotes(&command.key())).unwrap();
+        for option in &command.options {
+            writeln!(
+                out,
+                "            cand {} {}",
+                option.long,
+                doubled_quotes(&option.description)
+            )
+            .unwrap();
+        }
+        for subcommand in &command.subcommands {
+            writeln!(
+                out,
+                "            cand {} {}",
+                subcommand.name(),
+                doubled_quotes(&subcommand.description)
+            )
+            .unwrap();
+        }
+        out.push_str("        }\n");
+    }
+    out.push_str("    ]\n");
+    writeln!(out, "    var command = {}", doubled_quotes(PROGRAM)).unwrap();
+    out.push_str("    for word $words[1..-1] {\n");
+    out.push_str("        if (has-key $completions $command';'$word) {\n");
+    out.push_str("            set command = $command';'$word\n");
+    out.push_str("        }\n");
+    out.push_str("    }\n");
+    out.push_str("    $completions[$command]\n");
+    out.push_str("}\n");
+    out
+}
+
+fn powershell(tree: &Command) -> String {
+    let mut out = String::new();
+    out.push_str("using namespace System.Management.Automation\n");
+    out.push_str("using namespace System.Management.Automation.Language\n\n");
+    writeln!(
+        out,
+        "Register-ArgumentCompleter -Native -CommandName {} -ScriptBlock {{",
+        doubled_quotes(PROGRAM)
+    )
+    .unwrap();
+    out.push_str("    param($wordToComplete, $commandAst, $cursorPosition)\n\n");
+    writeln!(out, "    $known = @({})", keys(tree, doubled_quotes, ", ")).unwrap();
+    writeln!(out, "    $command = {}", doubled_quotes(PROGRAM)).unwrap();
+    out.push_str(
+        "    foreach ($element in $commandAst.CommandElements | Select-Object -Skip 1) {\n",
+    );
+    out.push_str("        if ($element -isnot [StringConstantExpressionAst]) { continue }\n");
+    out.push_str("        $next = \"$command;$($element.Value)\"\n");
+    out.push_str("        if ($known -contains $next) { $command = $next }\n");
+    out.push_str("    }\n\n");
+    out.push_str("    $completions = @(switch ($command) {\n");
+    for command in tree.walk() {
+        writeln!(out, "        {} {{", doubled_quotes(&command.key())).unwrap();
+        for option in &command.options {
+            writeln!(
+                out,
+                "            [CompletionResult]::new({}, {}, [CompletionResultType]::ParameterName, {})",
+                doubled_quotes(&option.long),
+                doubled_quotes(option.long.trim_start_matches('-')),
+                doubled_quotes(&option.description)
+            )
+            .unwrap();
+        }
+        for subcommand in &command.subcommands {
+            writeln!(
+                out,
+                "            [CompletionResult]::new({}, {}, [CompletionResultType]::ParameterValue, {})",
+                doubled_quotes(subcommand.name()),
+                doubled_quotes(subcommand.name()),
+                doubled_quotes(&subcommand.description)
+            )
+            .unwrap();
+        }
+        out.push_str("            break\n");
+        out.push_str("        }\n");
+    }
+    out.push_str("    })\n\n");
+    out.push_str("    $completions.Where{ $_.CompletionText -like \"$wordToComplete*\" } |\n");
+    out.push_str("        Sort-Object -Property ListItemText\n");
+    out.push_str("}\n");
+    out
+}
@@ -0,0 +1,235 @@
+//! Complex numbers with `f64` parts.
+
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+/// A complex number `re + im·i`.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub fn new(re: f64, im: f64) -> Self {
+        Complex { re, im }
+    }
+
+    /// The number with magnitude `r` at angle `theta` radians.
+    pub fn from_polar(r: f64, theta: f64) -> Self {
+        Complex::new(r * theta.cos(), r * theta.sin())
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.re == 0.0 && self.im == 0.0
+    }
+
+    pub fn is_finite(self) -> bool {
+        self.re.is_finite() && self.im.is_finite()
+    }
+
+    /// The magnitude `|z|`.
+    pub fn abs(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// The angle in radians, in `(-π, π]`.
+    pub fn arg(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    pub fn conj(self) -> Self {
+        Complex::new(self.re, -self.im)
+    }
+
+    /// The quotient, or `None` when `rhs` is zero.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.is_zero() {
+            return None;
+        }
+        let denom = rhs.re * rhs.re + rhs.im * rhs.im;
+        Some(Complex::new(
+            (self.re * rhs.re + self.im * rhs.im) / denom,
+            (self.im * rhs.re - self.re * rhs.im) / denom,
+        ))
+    }
+
+    /// Raises to an integer power by repeated squaring, which keeps results
+    /// such as `(1+i)^2 = 2i` exact. Returns `None` for zero to a negative
+    /// power.
+    pub fn powi(self, exp: i64) -> Option<Self> {
+        let mut base = self;
+        if exp < 0 {
+            base = Complex::new(1.0, 0.0).checked_div(self)?;
+        }
+        let mut result = Complex::new(1.0, 0.0);
+        let mut exp = exp.unsigned_abs();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+        Some(result)
+    }
+
+    /// The principal value of `self^exp`, or `None` for zero to a power
+    /// with a non-positive real part.
+    pub fn pow(self, exp: Self) -> Option<Self> {
+        if exp.im == 0.0 && exp.re.fract() == 0.0 && exp.re.abs() <= i64::MAX as f64 {
+            return self.powi(exp.re as i64);
+        }
+        if self.is_zero() {
+            return if exp.re > 0.0 {
+                Some(Complex::default())
+            } else {
+                None
+            };
+        }
+        // z^w = e^(w ln z)
+        let ln = Complex::new(self.abs().ln(), self.arg());
+        let power = exp * ln;
+        Some(Complex::from_polar(power.re.exp(), power.im))
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+
+    fn add(self, rhs: Complex) -> Complex {
+        Complex::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+
+    fn sub(self, rhs: Complex) -> Complex {
+        Complex::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+
+    fn mul(self, rhs: Complex) -> Complex {
+        Complex::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl Neg for Complex {
+    type Output = Complex;
+
+    fn neg(self) -> Complex {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+impl From<f64> for Complex {
+    fn from(re: f64) -> Self {
+        Complex::new(re, 0.0)
+    }
+}
+
+impl FromStr for Complex {
+    type Err = String;
+
+    /// Parses `3+4i`, `-2.5j`, `i`, a real number, or the polar forms
+    /// `5∠53.13°` (degrees) and `5∠0.9273` (radians).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((magnitude, angle)) = s.split_once('∠') {
+            let r = parse_part(magnitude)?;
+            let theta = match angle.strip_suffix('°') {
+                Some(degrees) => parse_part(degrees)?.to_radians(),
+                None => parse_part(angle)?,
+            };
+            return Ok(Complex::from_polar(r, theta));
+        }
+        let body = match s.strip_suffix(['i', 'j']) {
+            Some(body) => body,
+            None => return parse_part(s).map(Complex::from),
+        };
+        // The imaginary part starts at the last sign that is not leading and
+        // not part of an exponent such as `1e-3`.
+        let split = body
+            .char_indices()
+            .rev()
+            .find(|&(index, c)| {
+                (c == '+' || c == '-') && index > 0 && !body[..index].ends_with(['e', 'E'])
+            })
+            .map(|(index, _)| index);
+        let (re, im) = match split {
+            Some(index) => (parse_part(&body[..index])?, &body[index..]),
+            None => (0.0, body),
+        };
+        let im = match im {
+            "" | "+" => 1.0,
+            "-" => -1.0,
+            im => parse_part(im)?,
+        };
+        Ok(Complex::new(re, im))
+    }
+}
+
+fn parse_part(text: &str) -> Result<f64, String> {
+    text.trim()
+        .parse()
+        .map_err(|_| format!("`{}` is not a valid complex number part", text))
+}
+
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = if self.im == 0.0 {
+            self.re.to_string()
+        } else if self.re == 0.0 {
+            format!("{}i", self.im)
+        } else if self.im.is_sign_negative() {
+            format!("{}-{}i", self.re, -self.im)
+        } else {
+            format!("{}+{}i", self.re, self.im)
+        };
+        f.pad(&text)
+    }
+}
+
+/// How complex results are printed, chosen with `--complex-form`.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum ComplexForm {
+    /// `3+4i`
+    #[default]
+    Rectangular,
+    /// `5∠53.13010235415598°`
+    Polar,
+}
+
+impl ComplexForm {
+    /// Rewrites a complex result printed in rectangular form in this form.
+    pub fn format(&self, text: &str) -> String {
+        match (self, text.parse::<Complex>()) {
+            (ComplexForm::Polar, Ok(value)) => {
+                format!("{}∠{}°", value.abs(), value.arg().to_degrees())
+            }
+            _ => text.to_string(),
+        }
+    }
+}
+
+impl FromStr for ComplexForm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "rectangular" => Ok(ComplexForm::Rectangular),
+            "polar" => Ok(ComplexForm::Polar),
+            _ => Err(format!(
+                "unknown complex form `{}`, expected one of: rectangular, polar",
+                s
+            )),
+        }
+    }
+}
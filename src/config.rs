@@ -0,0 +1,165 @@
+//! Defaults for the global options, from a configuration file and
+//! `ARGH_DEMO_*` environment variables.
+//!
+//! The file is `$XDG_CONFIG_HOME/argh-demo/config.toml`, or `config.ini`
+//! beside it, unless `--config` names another. It holds `key = value`
+//! lines, optionally under a `[defaults]` header, so the same file reads as
+//! TOML or INI:
+//!
+//! ```text
+//! # ~/.config/argh-demo/config.toml
+//! [defaults]
+//! type = "f64"
+//! format = "json"
+//! ```
+//!
+//! Options given on the command line take priority over the environment,
+//! which takes priority over the file.
+//!
+//! `locale` is not read from `LANG` or `LC_NUMERIC`: only plain output
+//! follows it, and only when it is asked for, since other programs often
+//! read plain output too.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::decimal::RoundingMode;
+use crate::dirs;
+use crate::error::CalcError;
+use crate::locale::Locale;
+use crate::number::{NumType, Precision};
+use crate::output::Format;
+
+/// The settings a configuration can hold, with the environment variables
+/// that override them.
+const KEYS: [(&str, &str); 6] = [
+    ("type", "ARGH_DEMO_TYPE"),
+    ("precision", "ARGH_DEMO_PRECISION"),
+    ("scale", "ARGH_DEMO_SCALE"),
+    ("format", "ARGH_DEMO_FORMAT"),
+    ("rounding", "ARGH_DEMO_ROUNDING"),
+    ("locale", "ARGH_DEMO_LOCALE"),
+];
+
+/// Names a configuration file in place of the one in the config directory.
+pub const CONFIG_VARIABLE: &str = "ARGH_DEMO_CONFIG";
+
+/// Configured defaults; `None` where nothing is configured.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Defaults {
+    pub num_type: Option<NumType>,
+    pub precision: Option<Precision>,
+    pub scale: Option<u32>,
+    pub format: Option<Format>,
+    pub rounding: Option<RoundingMode>,
+    pub locale: Option<Locale>,
+}
+
+impl Defaults {
+    /// Reads the configuration file, then the environment. `path` is the
+    /// file given with `--config`, which must exist; without it
+    /// `$ARGH_DEMO_CONFIG` or the file in the config directory is read if
+    /// there is one.
+    pub fn load(path: Option<&str>) -> Result<Defaults, CalcError> {
+        let mut defaults = Defaults::default();
+        let given = path
+            .map(PathBuf::from)
+            .or_else(|| env::var_os(CONFIG_VARIABLE).map(PathBuf::from));
+        let found = match given {
+            Some(path) => Some(path),
+            None => dirs::config_dir().and_then(|dir| {
+                ["config.toml", "config.ini"]
+                    .iter()
+                    .map(|name| dir.join(name))
+                    .find(|path| path.is_file())
+            }),
+        };
+        if let Some(path) = found {
+            let source = path.display().to_string();
+            let text = fs::read_to_string(&path).map_err(|err| CalcError::Io {
+                path: source.clone(),
+                action: "read",
+                message: err.to_string(),
+            })?;
+            defaults.read_file(&source, &text)?;
+        }
+        for (key, variable) in KEYS {
+            if let Ok(value) = env::var(variable) {
+                defaults
+                    .set(key, &value)
+                    .map_err(|reason| CalcError::Usage(format!("${}: {}", variable, reason)))?;
+            }
+        }
+        Ok(defaults)
+    }
+
+    fn read_file(&mut self, source: &str, text: &str) -> Result<(), CalcError> {
+        let mut section = None;
+        for (index, line) in text.lines().enumerate() {
+            let invalid = |reason: String| CalcError::InvalidInput {
+                source: source.to_string(),
+                line: index + 1,
+                text: line.trim().to_string(),
+                ty: "setting",
+                reason,
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(['#', ';']) {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = Some(name.trim());
+                continue;
+            }
+            if let Some(name) = section.filter(|&name| name != "defaults") {
+                return Err(invalid(format!(
+                    "unknown section `{}`, expected [defaults]",
+                    name
+                )));
+            }
+            let (key, value_text) = line
+                .split_once('=')
+                .ok_or_else(|| invalid("expected `key = value`".to_string()))?;
+            self.set(key.trim(), value(value_text.trim()))
+                .map_err(invalid)?;
+        }
+        Ok(())
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "type" => self.num_type = Some(value.parse()?),
+            "precision" => self.precision = Some(value.parse()?),
+            "scale" => {
+                let scale = value
+                    .parse()
+                    .map_err(|_| format!("scale must be a number of digits, not `{}`", value))?;
+                self.scale = Some(scale);
+            }
+            "format" => self.format = Some(value.parse()?),
+            "rounding" => self.rounding = Some(value.parse()?),
+            "locale" => self.locale = Some(value.parse()?),
+            _ => {
+                let keys: Vec<&str> = KEYS.iter().map(|(key, _)| *key).collect();
+                return Err(format!(
+                    "unknown key `{}`, expected one of: {}",
+                    key,
+                    keys.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The value of a `key = value` line: a TOML string without its quotes,
+/// or INI text up to any comment.
+fn value(raw: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(end) = raw.strip_prefix(quote).and_then(|rest| rest.find(quote)) {
+            return &raw[1..=end];
+        }
+    }
+    raw.split(['#', ';']).next().unwrap_or_default().trim()
+}
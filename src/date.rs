@@ -0,0 +1,381 @@
+//! Calendar dates and instants, in the proleptic Gregorian calendar.
+//!
+//! Instants carry a fixed UTC offset such as `+02:00` rather than a time
+//! zone, so no time zone database is needed and results never depend on
+//! the machine they are computed on.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// 0000-01-01T00:00:00 and 9999-12-31T23:59:59 as seconds since the epoch:
+/// the times that can be written with four-digit years.
+const MIN_LOCAL: i64 = -719_528 * SECONDS_PER_DAY;
+const MAX_LOCAL: i64 = 2_932_897 * SECONDS_PER_DAY - 1;
+
+/// A day, such as `2026-10-17`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Date {
+    year: i64,
+    month: u32,
+    day: u32,
+}
+
+impl Date {
+    /// The date, or `None` if the month or day does not exist.
+    pub fn new(year: i64, month: u32, day: u32) -> Option<Date> {
+        if (1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day) {
+            Some(Date { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    /// The date `days` days after 1970-01-01.
+    pub fn from_days(days: i64) -> Date {
+        // Howard Hinnant's civil_from_days, with eras of 400 years.
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Date { year, month, day }
+    }
+
+    /// The number of days since 1970-01-01.
+    pub fn days(self) -> i64 {
+        let year = self.year - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = i64::from(self.month);
+        let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+        let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Today in UTC.
+    pub fn today() -> Date {
+        Instant::now().date()
+    }
+
+    pub fn year(self) -> i64 {
+        self.year
+    }
+
+    pub fn month(self) -> u32 {
+        self.month
+    }
+
+    pub fn day(self) -> u32 {
+        self.day
+    }
+
+    /// The ISO weekday, from 1 for Monday to 7 for Sunday.
+    pub fn weekday(self) -> u32 {
+        // 1970-01-01 was a Thursday.
+        ((self.days() + 3).rem_euclid(7) + 1) as u32
+    }
+
+    /// The day of the year, from 1.
+    pub fn ordinal(self) -> u32 {
+        (self.days()
+            - Date::new(self.year, 1, 1)
+                .expect("January 1st exists")
+                .days()
+            + 1) as u32
+    }
+
+    pub fn add_days(self, days: i64) -> Date {
+        Date::from_days(self.days() + days)
+    }
+
+    /// Moves by whole months, keeping the day where the month has it and
+    /// using the month's last day otherwise: 2026-01-31 plus one month is
+    /// 2026-02-28.
+    pub fn add_months(self, months: i64) -> Date {
+        let index = self.year * 12 + i64::from(self.month) - 1 + months;
+        let year = index.div_euclid(12);
+        let month = index.rem_euclid(12) as u32 + 1;
+        let day = self.day.min(days_in_month(year, month));
+        Date { year, month, day }
+    }
+
+    /// Moves to the `days`-th weekday, Monday to Friday, after this date, or
+    /// before it if `days` is negative.
+    pub fn add_business_days(self, days: i64) -> Date {
+        let step = days.signum();
+        // From a weekend, counting works as from the adjacent weekday that
+        // the count moves away from: Friday forward, Monday backward.
+        let mut date = match (self.weekday(), step) {
+            (6, 1) => self.add_days(-1),
+            (7, 1) => self.add_days(-2),
+            (6, -1) => self.add_days(2),
+            (7, -1) => self.add_days(1),
+            _ => self,
+        };
+        date = date.add_days(days / 5 * 7);
+        let mut rest = days % 5;
+        while rest != 0 {
+            date = date.add_days(step);
+            if date.weekday() <= 5 {
+                rest -= step;
+            }
+        }
+        date
+    }
+
+    /// The ISO 8601 week-numbering year and week, from 1 to 53. Weeks start
+    /// on Monday, and week 1 is the one with the year's first Thursday.
+    pub fn iso_week(self) -> (i64, u32) {
+        let thursday = self.add_days(4 - i64::from(self.weekday()));
+        (thursday.year, (thursday.ordinal() - 1) / 7 + 1)
+    }
+
+    /// Whole months from `self` to `other`, rounded toward zero.
+    pub fn months_until(self, other: Date) -> i64 {
+        let mut months =
+            (other.year - self.year) * 12 + i64::from(other.month) - i64::from(self.month);
+        if months > 0 && self.add_months(months) > other {
+            months -= 1;
+        } else if months < 0 && self.add_months(months) < other {
+            months += 1;
+        }
+        months
+    }
+
+    /// Weekdays, Monday to Friday, from `self` up to but not including
+    /// `other`; negative if `other` is earlier.
+    pub fn business_days_until(self, other: Date) -> i64 {
+        if other < self {
+            return -other.business_days_until(self);
+        }
+        let days = other.days() - self.days();
+        let mut count = days / 7 * 5;
+        let mut date = self.add_days(days / 7 * 7);
+        while date < other {
+            if date.weekday() <= 5 {
+                count += 1;
+            }
+            date = date.add_days(1);
+        }
+        count
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    /// Parses `YYYY-MM-DD`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("`{}` is not a date, expected YYYY-MM-DD", s);
+        let mut parts = s.splitn(3, '-');
+        let mut field = |digits: usize| {
+            parts
+                .next()
+                .filter(|part| part.len() == digits && part.bytes().all(|b| b.is_ascii_digit()))
+                .and_then(|part| part.parse::<u32>().ok())
+                .ok_or_else(invalid)
+        };
+        let (year, month, day) = (field(4)?, field(2)?, field(2)?);
+        Date::new(i64::from(year), month, day)
+            .ok_or_else(|| format!("`{}` is not a day of the calendar", s))
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&format!(
+            "{:04}-{:02}-{:02}",
+            self.year, self.month, self.day
+        ))
+    }
+}
+
+/// A fixed offset from UTC, in seconds east.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Offset(i32);
+
+impl Offset {
+    pub fn seconds(self) -> i32 {
+        self.0
+    }
+}
+
+impl FromStr for Offset {
+    type Err = String;
+
+    /// Parses `Z`, `UTC`, `+02:00`, `+0200` or `-05`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("`{}` is not a UTC offset, expected Z or ±HH:MM", s);
+        if s == "Z" || s == "UTC" {
+            return Ok(Offset(0));
+        }
+        let (sign, rest) = match s.as_bytes().first() {
+            Some(b'+') => (1, &s[1..]),
+            Some(b'-') => (-1, &s[1..]),
+            _ => return Err(invalid()),
+        };
+        let digits = rest.replace(':', "");
+        if !matches!(digits.len(), 2 | 4) || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        let hours: i32 = digits[..2].parse().map_err(|_| invalid())?;
+        let minutes: i32 = digits
+            .get(2..)
+            .unwrap_or("0")
+            .parse()
+            .map_err(|_| invalid())?;
+        if hours > 23 || minutes > 59 {
+            return Err(invalid());
+        }
+        Ok(Offset(sign * (hours * 3600 + minutes * 60)))
+    }
+}
+
+impl fmt::Display for Offset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == 0 {
+            return f.write_str("Z");
+        }
+        let sign = if self.0 < 0 { '-' } else { '+' };
+        let minutes = self.0.abs() / 60;
+        write!(f, "{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+    }
+}
+
+/// A moment in time to the second, seen at a fixed UTC offset.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Instant {
+    /// Seconds since 1970-01-01T00:00:00Z.
+    timestamp: i64,
+    offset: Offset,
+}
+
+impl Instant {
+    /// The instant `timestamp` seconds after 1970-01-01T00:00:00Z, seen at
+    /// `offset`, or `None` if the time there is outside the years 0000 to
+    /// 9999.
+    pub fn from_timestamp(timestamp: i64, offset: Offset) -> Option<Instant> {
+        let local = timestamp.checked_add(i64::from(offset.seconds()))?;
+        if (MIN_LOCAL..=MAX_LOCAL).contains(&local) {
+            Some(Instant { timestamp, offset })
+        } else {
+            None
+        }
+    }
+
+    /// Now, in UTC.
+    pub fn now() -> Instant {
+        let timestamp = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => elapsed.as_secs() as i64,
+            Err(err) => -(err.duration().as_secs() as i64),
+        };
+        Instant::from_timestamp(timestamp, Offset::default())
+            .expect("the clock is within the years 0000 to 9999")
+    }
+
+    /// Seconds since 1970-01-01T00:00:00Z.
+    pub fn timestamp(self) -> i64 {
+        self.timestamp
+    }
+
+    /// Seconds since 1970-01-01T00:00:00 at the instant's offset, which
+    /// `from_timestamp` keeps from overflowing.
+    fn local(self) -> i64 {
+        self.timestamp + i64::from(self.offset.seconds())
+    }
+
+    /// The date at the instant's offset.
+    pub fn date(self) -> Date {
+        Date::from_days(self.local().div_euclid(SECONDS_PER_DAY))
+    }
+}
+
+impl FromStr for Instant {
+    type Err = String;
+
+    /// Parses RFC 3339 such as `2026-10-17T12:30:00+02:00`, allowing a
+    /// space for the `T`, leaving out the seconds or the offset (UTC), or
+    /// giving only the date (midnight UTC).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "`{}` is not a time, expected YYYY-MM-DD or YYYY-MM-DDTHH:MM:SS±HH:MM",
+                s
+            )
+        };
+        let (date, time) = match s.split_once(['T', 't', ' ']) {
+            Some((date, time)) => (date, time),
+            None => (s, "00:00"),
+        };
+        let date: Date = date.parse()?;
+        let split = time.find(['Z', 'z', '+', '-']).unwrap_or(time.len());
+        let (clock, offset) = time.split_at(split);
+        let offset = match offset {
+            "" => Offset::default(),
+            "z" => Offset(0),
+            offset => offset.parse()?,
+        };
+        let fields = clock
+            .split(':')
+            .map(|field| {
+                if field.len() == 2 && field.bytes().all(|b| b.is_ascii_digit()) {
+                    field.parse::<i64>().map_err(|_| invalid())
+                } else {
+                    Err(invalid())
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let (hours, minutes, seconds) = match fields[..] {
+            [hours, minutes] => (hours, minutes, 0),
+            [hours, minutes, seconds] => (hours, minutes, seconds),
+            _ => return Err(invalid()),
+        };
+        if hours > 23 || minutes > 59 || seconds > 59 {
+            return Err(invalid());
+        }
+        let local = date.days() * SECONDS_PER_DAY + hours * 3600 + minutes * 60 + seconds;
+        Instant::from_timestamp(local - i64::from(offset.seconds()), offset).ok_or_else(invalid)
+    }
+}
+
+impl fmt::Display for Instant {
+    /// Formats as RFC 3339, such as `2026-10-17T12:30:00+02:00`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.local().rem_euclid(SECONDS_PER_DAY);
+        write!(
+            f,
+            "{}T{:02}:{:02}:{:02}{}",
+            self.date(),
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60,
+            self.offset
+        )
+    }
+}
@@ -0,0 +1,271 @@
+//! Exact fixed-point decimal numbers.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::bignum::BigInt;
+
+/// Largest power of ten accepted in a literal, to keep parsing cheap.
+const MAX_EXPONENT: i64 = 100_000;
+
+/// Most fractional digits a result may be asked to keep, with `--scale`,
+/// `--as-decimal` or by a power; like `MAX_EXPONENT`, it keeps results cheap
+/// to compute and print.
+pub const MAX_SCALE: u32 = 100_000;
+
+/// Fractional digits kept by a division when no scale is requested.
+pub const DEFAULT_DIV_SCALE: u32 = 20;
+
+/// How digits beyond the requested scale are dropped.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum RoundingMode {
+    /// Round to nearest, ties to the even neighbour (banker's rounding).
+    #[default]
+    HalfEven,
+    /// Round to nearest, ties away from zero.
+    HalfUp,
+    /// Drop the extra digits, rounding toward zero.
+    Truncate,
+}
+
+impl FromStr for RoundingMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "half-even" => Ok(RoundingMode::HalfEven),
+            "half-up" => Ok(RoundingMode::HalfUp),
+            "truncate" => Ok(RoundingMode::Truncate),
+            _ => Err(format!(
+                "unknown rounding mode `{}`, expected one of: half-even, half-up, truncate",
+                s
+            )),
+        }
+    }
+}
+
+/// Divides `numerator` by a non-zero `denominator`, rounding the quotient to
+/// an integer with `mode`.
+pub fn div_rounded(numerator: &BigInt, denominator: &BigInt, mode: RoundingMode) -> BigInt {
+    let (quotient, remainder) = numerator
+        .div_rem(denominator)
+        .expect("denominator must not be zero");
+    if remainder.is_zero() {
+        return quotient;
+    }
+    let twice = &remainder.abs() * &BigInt::from(2i64);
+    let away = match mode {
+        RoundingMode::Truncate => false,
+        RoundingMode::HalfUp => twice >= denominator.abs(),
+        RoundingMode::HalfEven => match twice.cmp(&denominator.abs()) {
+            Ordering::Greater => true,
+            Ordering::Equal => quotient.is_odd(),
+            Ordering::Less => false,
+        },
+    };
+    if !away {
+        quotient
+    } else if numerator.is_negative() != denominator.is_negative() {
+        &quotient - &BigInt::from(1i64)
+    } else {
+        &quotient + &BigInt::from(1i64)
+    }
+}
+
+/// A decimal number `unscaled * 10^-scale`, held exactly.
+#[derive(Clone, Debug)]
+pub struct Decimal {
+    unscaled: BigInt,
+    scale: u32,
+}
+
+impl Decimal {
+    pub fn new(unscaled: BigInt, scale: u32) -> Self {
+        Decimal { unscaled, scale }
+    }
+
+    /// The unscaled integer and the scale.
+    pub fn parts(&self) -> (&BigInt, u32) {
+        (&self.unscaled, self.scale)
+    }
+
+    /// Rescales to exactly `scale` fractional digits, rounding with `mode`
+    /// when digits have to be dropped.
+    pub fn round(&self, scale: u32, mode: RoundingMode) -> Decimal {
+        match self.scale.cmp(&scale) {
+            Ordering::Equal => self.clone(),
+            Ordering::Less => {
+                Decimal::new(&self.unscaled * &BigInt::pow10(scale - self.scale), scale)
+            }
+            Ordering::Greater => Decimal::new(
+                div_rounded(&self.unscaled, &BigInt::pow10(self.scale - scale), mode),
+                scale,
+            ),
+        }
+    }
+
+    /// Brings both operands to their common (larger) scale.
+    pub fn align(&self, other: &Decimal) -> (BigInt, BigInt, u32) {
+        let scale = self.scale.max(other.scale);
+        let lhs = self.round(scale, RoundingMode::Truncate);
+        let rhs = other.round(scale, RoundingMode::Truncate);
+        (lhs.unscaled, rhs.unscaled, scale)
+    }
+
+    pub fn add(&self, rhs: &Decimal) -> Decimal {
+        let (lhs, rhs, scale) = self.align(rhs);
+        Decimal::new(&lhs + &rhs, scale)
+    }
+
+    pub fn sub(&self, rhs: &Decimal) -> Decimal {
+        let (lhs, rhs, scale) = self.align(rhs);
+        Decimal::new(&lhs - &rhs, scale)
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.unscaled.is_negative()
+    }
+
+    pub fn abs(&self) -> Decimal {
+        Decimal::new(self.unscaled.abs(), self.scale)
+    }
+
+    pub fn mul(&self, rhs: &Decimal) -> Decimal {
+        Decimal::new(&self.unscaled * &rhs.unscaled, self.scale + rhs.scale)
+    }
+
+    /// Divides, keeping `scale` fractional digits. Without a scale the
+    /// quotient keeps up to `DEFAULT_DIV_SCALE` digits, minus trailing zeros.
+    /// Returns `None` when `rhs` is zero.
+    pub fn div(&self, rhs: &Decimal, scale: Option<u32>, mode: RoundingMode) -> Option<Decimal> {
+        if rhs.unscaled.is_zero() {
+            return None;
+        }
+        let target = scale.unwrap_or_else(|| self.scale.max(rhs.scale).max(DEFAULT_DIV_SCALE));
+        let shift = i64::from(target) + i64::from(rhs.scale) - i64::from(self.scale);
+        let quotient = if shift >= 0 {
+            let numerator = &self.unscaled * &BigInt::pow10(shift as u32);
+            div_rounded(&numerator, &rhs.unscaled, mode)
+        } else {
+            let denominator = &rhs.unscaled * &BigInt::pow10((-shift) as u32);
+            div_rounded(&self.unscaled, &denominator, mode)
+        };
+        let quotient = Decimal::new(quotient, target);
+        Some(match scale {
+            Some(_) => quotient,
+            None => quotient.normalize(),
+        })
+    }
+
+    /// The remainder of a division truncated toward zero, or `None` when
+    /// `rhs` is zero.
+    pub fn rem(&self, rhs: &Decimal) -> Option<Decimal> {
+        let (lhs, rhs, scale) = self.align(rhs);
+        let (_, remainder) = lhs.div_rem(&rhs)?;
+        Some(Decimal::new(remainder, scale))
+    }
+
+    pub fn pow(&self, exp: u32) -> Option<Decimal> {
+        let scale = self.scale.checked_mul(exp)?;
+        Some(Decimal::new(self.unscaled.pow(exp), scale))
+    }
+
+    /// The value as an integer, if it has no fractional part.
+    pub fn to_integer(&self) -> Option<BigInt> {
+        let (quotient, remainder) = self.unscaled.div_rem(&BigInt::pow10(self.scale))?;
+        if remainder.is_zero() {
+            Some(quotient)
+        } else {
+            None
+        }
+    }
+
+    /// Drops trailing fractional zeros.
+    pub fn normalize(&self) -> Decimal {
+        let ten = BigInt::from(10i64);
+        let mut unscaled = self.unscaled.clone();
+        let mut scale = self.scale;
+        while scale > 0 {
+            let (quotient, remainder) = unscaled.div_rem(&ten).expect("ten is not zero");
+            if !remainder.is_zero() {
+                break;
+            }
+            unscaled = quotient;
+            scale -= 1;
+        }
+        Decimal::new(unscaled, scale)
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        let (lhs, rhs, _) = self.align(other);
+        lhs == rhs
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mantissa, exponent) = match s.find(['e', 'E']) {
+            Some(index) => {
+                let exponent: i64 = s[index + 1..]
+                    .parse()
+                    .map_err(|_| format!("invalid exponent in `{}`", s))?;
+                (&s[..index], exponent)
+            }
+            None => (s, 0),
+        };
+        let (int_part, frac_part) = match mantissa.find('.') {
+            Some(index) => (&mantissa[..index], &mantissa[index + 1..]),
+            None => (mantissa, ""),
+        };
+        let digits_only = int_part.trim_start_matches(['+', '-']);
+        if digits_only.is_empty() && frac_part.is_empty() {
+            return Err("cannot parse decimal from empty string".to_string());
+        }
+        if !frac_part.bytes().all(|b| b.is_ascii_digit()) {
+            return Err("invalid digit found in string".to_string());
+        }
+        let sign_and_int = if digits_only.is_empty() {
+            format!("{}0", int_part)
+        } else {
+            int_part.to_string()
+        };
+        let unscaled: BigInt = format!("{}{}", sign_and_int, frac_part).parse()?;
+        let scale = frac_part.len() as i64 - exponent;
+        if scale.abs() > MAX_EXPONENT {
+            return Err("exponent out of range".to_string());
+        }
+        if scale >= 0 {
+            Ok(Decimal::new(unscaled, scale as u32))
+        } else {
+            Ok(Decimal::new(&unscaled * &BigInt::pow10((-scale) as u32), 0))
+        }
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.unscaled.abs().to_string();
+        let scale = self.scale as usize;
+        let mut text = String::new();
+        if self.unscaled.is_negative() {
+            text.push('-');
+        }
+        if scale == 0 {
+            text.push_str(&digits);
+        } else {
+            // Format widths are limited to u16, so the zeros are built by hand.
+            let zeros = (scale + 1).saturating_sub(digits.len());
+            let padded = "0".repeat(zeros) + &digits;
+            let (int_part, frac_part) = padded.split_at(padded.len() - scale);
+            text.push_str(int_part);
+            text.push('.');
+            text.push_str(frac_part);
+        }
+        f.pad(&text)
+    }
+}
@@ -0,0 +1,29 @@
+//! Per-user directories, following the XDG base directory specification.
+
+use std::env;
+use std::path::PathBuf;
+
+const APP_DIR: &str = "argh-demo";
+
+/// Where persistent state such as history is kept:
+/// `$XDG_DATA_HOME/argh-demo`, falling back to `~/.local/share/argh-demo`.
+pub fn data_dir() -> Option<PathBuf> {
+    base_dir("XDG_DATA_HOME", &[".local", "share"]).map(|dir| dir.join(APP_DIR))
+}
+
+/// Where the configuration file is looked for: `$XDG_CONFIG_HOME/argh-demo`,
+/// falling back to `~/.config/argh-demo`.
+pub fn config_dir() -> Option<PathBuf> {
+    base_dir("XDG_CONFIG_HOME", &[".config"]).map(|dir| dir.join(APP_DIR))
+}
+
+fn base_dir(variable: &str, fallback: &[&str]) -> Option<PathBuf> {
+    // The specification says relative paths in these variables are invalid.
+    if let Some(dir) = env::var_os(variable).map(PathBuf::from) {
+        if dir.is_absolute() {
+            return Some(dir);
+        }
+    }
+    let home = env::var_os("HOME").map(PathBuf::from)?;
+    Some(fallback.iter().fold(home, |dir, part| dir.join(part)))
+}
@@ -0,0 +1,137 @@
+//! The man page and Markdown command reference, generated from the
+//! commands in `help::command_tree` by the hidden `gen-docs` subcommand,
+//! which also refreshes the `--help` output quoted in the READMEs.
+
+use std::fmt::Write;
+
+use argh::FromArgs;
+
+use crate::cli::DemoCli;
+use crate::help::{self, Command, PROGRAM};
+
+/// Where the generated files are checked in, relative to the repository.
+pub const MAN_PAGE: &str = "docs/argh-demo.1";
+pub const REFERENCE: &str = "docs/reference.md";
+pub const READMES: [&str; 2] = ["README.md", "README-zh.md"];
+
+/// Mark the `--help` output in the READMEs.
+const USAGE_START: &str =
+    "<!-- Generated by `argh-demo gen-docs` from `--help`; do not edit. -->\n";
+const USAGE_END: &str = "<!-- End of generated `--help`. -->\n";
+
+/// The man page, in troff with the `man` macros.
+pub fn man_page() -> String {
+    let tree = help::command_tree();
+    let mut out = String::new();
+    writeln!(
+        out,
+        ".TH {} 1 \"\" \"{} {}\" \"User Commands\"",
+        PROGRAM.to_uppercase(),
+        PROGRAM,
+        env!("CARGO_PKG_VERSION")
+    )
+    .unwrap();
+    out.push_str(".SH NAME\n");
+    writeln!(out, "{} \\- {}", PROGRAM, troff(&tree.description)).unwrap();
+    out.push_str(".SH SYNOPSIS\n");
+    writeln!(out, "{}", troff(&tree.synopsis)).unwrap();
+    out.push_str(".SH DESCRIPTION\n");
+    writeln!(out, "{}", troff(&tree.description)).unwrap();
+    out.push_str(".SH OPTIONS\n");
+    man_options(&mut out, &tree);
+    out.push_str(".SH COMMANDS\n");
+    for command in &tree.walk()[1..] {
+        writeln!(out, ".SS \"{}\"", troff(&command.path.join(" "))).unwrap();
+        writeln!(out, "{}", troff(&command.description)).unwrap();
+        out.push_str(".PP\n");
+        writeln!(out, "Usage: {}", troff(&command.synopsis)).unwrap();
+        man_options(&mut out, command);
+    }
+    out
+}
+
+fn man_options(out: &mut String, command: &Command) {
+    for option in &command.options {
+        out.push_str(".TP\n");
+        writeln!(out, ".B {}", troff(&option.long)).unwrap();
+        writeln!(out, "{}", troff(&option.description)).unwrap();
+    }
+}
+
+/// Escapes text for troff: backslashes and dashes, and a leading `.` or
+/// `'` that would start a request.
+fn troff(text: &str) -> String {
+    let text = text.replace('\\', "\\e").replace('-', "\\-");
+    if text.starts_with(['.', '\'']) {
+        format!("\\&{}", text)
+    } else {
+        text
+    }
+}
+
+/// The Markdown reference: one section per command, with its usage, its
+/// options and links to its subcommands.
+pub fn reference() -> String {
+    let tree = help::command_tree();
+    let mut out = String::new();
+    writeln!(out, "# {} command reference\n", PROGRAM).unwrap();
+    writeln!(
+        out,
+        "<!-- Generated by `{} gen-docs` from the command definitions; do not edit. -->",
+        PROGRAM
+    )
+    .unwrap();
+    for command in tree.walk() {
+        let title = command.path.join(" ");
+        writeln!(out, "\n## {}\n", title).unwrap();
+        writeln!(out, "{}\n", command.description).unwrap();
+        writeln!(out, "```text\n{}\n```", command.synopsis).unwrap();
+        if !command.options.is_empty() {
+            out.push_str("\n| Option | Description |\n| --- | --- |\n");
+            for option in &command.options {
+                writeln!(
+                    out,
+                    "| `{}` | {} |",
+                    option.long,
+                    option.description.replace('|', "\\|")
+                )
+                .unwrap();
+            }
+        }
+        if !command.subcommands.is_empty() {
+            out.push_str("\nCommands:\n\n");
+            for subcommand in &command.subcommands {
+                writeln!(
+                    out,
+                    "- [`{}`](#{}): {}",
+                    subcommand.name(),
+                    subcommand.path.join("-"),
+                    subcommand.description
+                )
+                .unwrap();
+            }
+        }
+    }
+    out
+}
+
+/// The top-level `--help` output, run as the READMEs run the program.
+pub fn usage() -> String {
+    match DemoCli::from_args(&["target/debug/argh-demo"], &["--help"]) {
+        Err(exit) => exit.output,
+        Ok(_) => unreachable!("--help always exits early"),
+    }
+}
+
+/// `readme` with the block between its usage markers replaced by `usage`,
+/// or `None` if it has no markers.
+pub fn readme(readme: &str) -> Option<String> {
+    let start = readme.find(USAGE_START)? + USAGE_START.len();
+    let end = start + readme[start..].find(USAGE_END)?;
+    Some(format!(
+        "{}```text\n{}\n```\n{}",
+        &readme[..start],
+        usage().trim_end(),
+        &readme[end..]
+    ))
+}
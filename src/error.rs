@@ -0,0 +1,182 @@
+//! Errors reported by the calculator.
+
+use std::fmt;
+
+/// Why a calculation could not produce a result.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalcError {
+    /// The exact result does not fit in the operand type.
+    Overflow {
+        op: &'static str,
+        lhs: String,
+        rhs: String,
+        ty: &'static str,
+    },
+    /// A division or remainder by zero, or zero raised to a negative power.
+    DivisionByZero {
+        op: &'static str,
+        lhs: String,
+        rhs: String,
+    },
+    /// An integer raised to a negative power.
+    NegativeExponent { exp: String, ty: &'static str },
+    /// The operation is undefined for its operands.
+    Domain {
+        op: &'static str,
+        lhs: String,
+        rhs: String,
+        reason: &'static str,
+    },
+    /// A quantity cannot be converted to a unit of another dimension.
+    Dimension {
+        value: String,
+        from: &'static str,
+        unit: &'static str,
+        to: &'static str,
+    },
+    /// An operand is not a valid value of the selected type.
+    InvalidNumber {
+        text: String,
+        ty: &'static str,
+        reason: String,
+    },
+    /// A value read from a file or stdin is not valid; `line` is 1-based.
+    InvalidInput {
+        source: String,
+        line: usize,
+        text: String,
+        ty: &'static str,
+        reason: String,
+    },
+    /// A file could not be read or written; `action` is `read` or `write`.
+    Io {
+        path: String,
+        action: &'static str,
+        message: String,
+    },
+    /// An expression could not be parsed; `column` is 1-based.
+    Syntax {
+        input: String,
+        column: usize,
+        message: String,
+    },
+    /// The options given cannot be used together.
+    Usage(String),
+    /// A statement of a script failed; `line` is 1-based.
+    Statement {
+        source: String,
+        line: usize,
+        error: Box<CalcError>,
+    },
+    /// Statements of a script run with `--keep-going` failed; the first of
+    /// them decides the exit code.
+    Failed {
+        source: String,
+        count: usize,
+        first: Box<CalcError>,
+    },
+}
+
+impl CalcError {
+    /// The process exit code used when this error ends the program.
+    ///
+    /// Argument parsing failures exit with 1, so every variant here uses a
+    /// distinct code above that.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CalcError::Usage(_)
+            | CalcError::InvalidNumber { .. }
+            | CalcError::InvalidInput { .. }
+            | CalcError::Syntax { .. } => 2,
+            CalcError::Overflow { .. } => 3,
+            CalcError::DivisionByZero { .. }
+            | CalcError::NegativeExponent { .. }
+            | CalcError::Domain { .. }
+            | CalcError::Dimension { .. } => 4,
+            CalcError::Io { .. } => 5,
+            CalcError::Statement { error, .. } => error.exit_code(),
+            CalcError::Failed { first, .. } => first.exit_code(),
+        }
+    }
+}
+
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalcError::Overflow { op, lhs, rhs, ty } => write!(
+                f,
+                "{} {} {} overflows {} (use --wrapping or --saturating to allow it)",
+                lhs, op, rhs, ty
+            ),
+            CalcError::DivisionByZero { op: "^", lhs, rhs } => {
+                write!(f, "{} ^ {} raises zero to a negative power", lhs, rhs)
+            }
+            CalcError::DivisionByZero { op, lhs, rhs } => {
+                write!(f, "{} {} {} divides by zero", lhs, op, rhs)
+            }
+            CalcError::NegativeExponent { exp, ty } => {
+                write!(f, "negative exponent {} is not supported for {}", exp, ty)
+            }
+            CalcError::Domain {
+                op,
+                lhs,
+                rhs,
+                reason,
+            } => write!(f, "{} {} {} is undefined: {}", lhs, op, rhs, reason),
+            CalcError::Dimension {
+                value,
+                from,
+                unit,
+                to,
+            } => write!(
+                f,
+                "cannot convert {} ({}) to {} ({})",
+                value, from, unit, to
+            ),
+            CalcError::InvalidNumber { text, ty, reason } => {
+                write!(f, "`{}` is not a valid {}: {}", text, ty, reason)
+            }
+            CalcError::InvalidInput {
+                source,
+                line,
+                text,
+                ty,
+                reason,
+            } => write!(
+                f,
+                "{}:{}: `{}` is not a valid {}: {}",
+                source, line, text, ty, reason
+            ),
+            CalcError::Io {
+                path,
+                action,
+                message,
+            } => write!(f, "cannot {} {}: {}", action, path, message),
+            CalcError::Syntax {
+                input,
+                column,
+                message,
+            } => write!(
+                f,
+                "{} at column {}\n  {}\n  {:>width$}",
+                message,
+                column,
+                input,
+                "^",
+                width = column
+            ),
+            CalcError::Usage(message) => f.write_str(message),
+            CalcError::Statement {
+                source,
+                line,
+                error,
+            } => write!(f, "{}:{}: {}", source, line, error),
+            CalcError::Failed { source, count, .. } => {
+                let plural = if *count == 1 { "" } else { "s" };
+                write!(f, "{} statement{} of {} failed", count, plural, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CalcError {}
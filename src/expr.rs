@@ -0,0 +1,396 @@
+//! Infix expressions: a tokenizer, a precedence-climbing parser and an
+//! evaluator over any `Number` type.
+//!
+//! From loosest to tightest binding the operators are `+ -`, `* / %`, unary
+//! minus and `^`, so `-2^2` is `-(2^2)`. `^` is right-associative, the others
+//! associate to the left.
+//!
+//! Identifiers refer to variables supplied by the caller, such as `ans` in
+//! the REPL.
+
+use std::cell::Cell;
+use std::collections::BTreeMap;
+
+use crate::error::CalcError;
+use crate::number::{Number, Rounding};
+use crate::ops::{self, Operation, Overflow};
+use crate::radix;
+
+const PREC_UNARY: u8 = 3;
+
+/// How deeply parentheses, unary operators and binary operators may nest.
+/// `1 + 2 + 3` nests as `(1 + 2) + 3`, so each operator of a chain counts
+/// as a level. Parsing, evaluating and dropping an expression all recurse
+/// once per level, so this keeps hostile input from exhausting the stack.
+const MAX_DEPTH: usize = 256;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(String),
+    Ident(String),
+    Op(Operation),
+    LParen,
+    RParen,
+    End,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Lexeme {
+    token: Token,
+    /// 1-based column of the token's first character.
+    column: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    Literal {
+        text: String,
+        column: usize,
+    },
+    Variable {
+        name: String,
+        column: usize,
+    },
+    Neg(Box<Node>),
+    Binary {
+        op: Operation,
+        lhs: Box<Node>,
+        rhs: Box<Node>,
+    },
+}
+
+/// A parsed expression, ready to be evaluated with any numeric type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expression {
+    source: String,
+    root: Node,
+}
+
+impl Expression {
+    pub fn parse(source: &str) -> Result<Self, CalcError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            source,
+            tokens,
+            pos: 0,
+            depth: 0,
+        };
+        let root = parser.parse_expr(0)?;
+        let next = parser.next();
+        match next.token {
+            Token::End => Ok(Expression {
+                source: source.to_string(),
+                root,
+            }),
+            Token::RParen => Err(syntax_error(source, next.column, "unmatched `)`")),
+            _ => Err(syntax_error(source, next.column, "expected an operator")),
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluates the expression, also reporting whether any step wrapped or
+    /// saturated.
+    pub fn eval<T: Number>(
+        &self,
+        overflow: Overflow,
+        rounding: &Rounding,
+    ) -> Result<(T, bool), CalcError> {
+        self.eval_with(overflow, rounding, &BTreeMap::new())
+    }
+
+    /// Like `eval`, with `variables` providing the values of identifiers.
+    pub fn eval_with<T: Number>(
+        &self,
+        overflow: Overflow,
+        rounding: &Rounding,
+        variables: &BTreeMap<String, T>,
+    ) -> Result<(T, bool), CalcError> {
+        let context = EvalContext {
+            source: &self.source,
+            overflow,
+            rounding,
+            variables,
+            overflowed: Cell::new(false),
+        };
+        let result = context.eval(&self.root)?;
+        Ok((result.round(rounding), context.overflowed.get()))
+    }
+}
+
+fn syntax_error(source: &str, column: usize, message: &str) -> CalcError {
+    CalcError::Syntax {
+        input: source.to_string(),
+        column,
+        message: message.to_string(),
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Lexeme>, CalcError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let column = i + 1;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && is_literal_char(chars[i]) {
+                i += 1;
+                // Keep the sign of an exponent such as `1e-3` in the literal.
+                if i + 1 < chars.len()
+                    && (chars[i] == '+' || chars[i] == '-')
+                    && is_exponent_marker(&chars[start..i])
+                {
+                    i += 1;
+                }
+            }
+            let text = chars[start..i].iter().collect();
+            tokens.push(Lexeme {
+                token: Token::Number(text),
+                column,
+            });
+            continue;
+        }
+        if is_identifier_start(c) {
+            let start = i;
+            while i < chars.len() && is_identifier_char(chars[i]) {
+                i += 1;
+            }
+            let name = chars[start..i].iter().collect();
+            tokens.push(Lexeme {
+                token: Token::Ident(name),
+                column,
+            });
+            continue;
+        }
+        let token = match c {
+            '+' => Token::Op(Operation::Add),
+            '-' => Token::Op(Operation::Sub),
+            '*' => Token::Op(Operation::Mul),
+            '/' => Token::Op(Operation::Div),
+            '%' => Token::Op(Operation::Rem),
+            '^' => Token::Op(Operation::Pow),
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            _ => {
+                return Err(syntax_error(
+                    source,
+                    column,
+                    &format!("unexpected character `{}`", c),
+                ))
+            }
+        };
+        tokens.push(Lexeme { token, column });
+        i += 1;
+    }
+    tokens.push(Lexeme {
+        token: Token::End,
+        column: chars.len() + 1,
+    });
+    Ok(tokens)
+}
+
+/// Splits `name = expression` into the name and the expression, or gives
+/// `None` when `line` is not an assignment.
+pub fn split_assignment(line: &str) -> Option<(&str, &str)> {
+    let index = line.find('=')?;
+    let name = line[..index].trim();
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(is_identifier_start) && chars.all(is_identifier_char);
+    if valid {
+        Some((name, line[index + 1..].trim()))
+    } else {
+        None
+    }
+}
+
+/// Whether `c` can start a variable name.
+pub fn is_identifier_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+pub fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn is_literal_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '.' || c == '_'
+}
+
+/// Whether `literal` is a decimal mantissa followed by `e`, so that a sign
+/// after it belongs to the exponent.
+fn is_exponent_marker(literal: &[char]) -> bool {
+    match literal.split_last() {
+        Some((last, mantissa)) => {
+            (*last == 'e' || *last == 'E')
+                && !mantissa.is_empty()
+                && mantissa.iter().all(|c| c.is_ascii_digit() || *c == '.')
+        }
+        None => false,
+    }
+}
+
+fn precedence(op: Operation) -> (u8, bool) {
+    match op {
+        Operation::Add | Operation::Sub => (1, false),
+        Operation::Mul | Operation::Div | Operation::Rem | Operation::RemEuclid => (2, false),
+        Operation::Pow => (4, true),
+    }
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Lexeme>,
+    pos: usize,
+    /// Calls of `parse_expr` in progress.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> &Lexeme {
+        &self.tokens[self.pos]
+    }
+
+    fn next(&mut self) -> Lexeme {
+        let lexeme = self.tokens[self.pos].clone();
+        if lexeme.token != Token::End {
+            self.pos += 1;
+        }
+        lexeme
+    }
+
+    /// Parses operators binding at least as tightly as `min_prec`.
+    fn parse_expr(&mut self, min_prec: u8) -> Result<Node, CalcError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(syntax_error(
+                self.source,
+                self.peek().column,
+                "expression is too deeply nested",
+            ));
+        }
+        let depth = self.depth;
+        self.depth += 1;
+        let node = self.parse_binary(min_prec);
+        self.depth = depth;
+        node
+    }
+
+    fn parse_binary(&mut self, min_prec: u8) -> Result<Node, CalcError> {
+        let mut lhs = self.parse_prefix()?;
+        while let Token::Op(op) = self.peek().token {
+            let (prec, right_assoc) = precedence(op);
+            if prec < min_prec {
+                break;
+            }
+            self.next();
+            // Each operator nests what came before one level deeper.
+            self.depth += 1;
+            let rhs = self.parse_expr(if right_assoc { prec } else { prec + 1 })?;
+            lhs = Node::Binary {
+                op,
+                lhs: Box::new(lhs),
+                rhs: Box::new(rhs),
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn parse_prefix(&mut self) -> Result<Node, CalcError> {
+        let lexeme = self.next();
+        match lexeme.token {
+            Token::Number(text) => Ok(Node::Literal {
+                text,
+                column: lexeme.column,
+            }),
+            Token::Ident(name) => Ok(Node::Variable {
+                name,
+                column: lexeme.column,
+            }),
+            Token::Op(Operation::Sub) => Ok(Node::Neg(Box::new(self.parse_expr(PREC_UNARY)?))),
+            Token::Op(Operation::Add) => self.parse_expr(PREC_UNARY),
+            Token::LParen => {
+                let inner = self.parse_expr(0)?;
+                let close = self.next();
+                match close.token {
+                    Token::RParen => Ok(inner),
+                    Token::End => Err(syntax_error(self.source, lexeme.column, "unclosed `(`")),
+                    _ => Err(syntax_error(
+                        self.source,
+                        close.column,
+                        "expected an operator or `)`",
+                    )),
+                }
+            }
+            Token::End => Err(syntax_error(
+                self.source,
+                lexeme.column,
+                "unexpected end of expression",
+            )),
+            Token::Op(_) | Token::RParen => Err(syntax_error(
+                self.source,
+                lexeme.column,
+                "expected a number or `(`",
+            )),
+        }
+    }
+}
+
+struct EvalContext<'a, T> {
+    source: &'a str,
+    overflow: Overflow,
+    rounding: &'a Rounding,
+    variables: &'a BTreeMap<String, T>,
+    overflowed: Cell<bool>,
+}
+
+impl<'a, T: Number> EvalContext<'a, T> {
+    fn eval(&self, node: &Node) -> Result<T, CalcError> {
+        match node {
+            Node::Literal { text, column } => self.parse_literal(text, *column),
+            Node::Variable { name, column } => self.variables.get(name).cloned().ok_or_else(|| {
+                syntax_error(
+                    self.source,
+                    *column,
+                    &format!("unknown variable `{}`", name),
+                )
+            }),
+            // Parse `-literal` directly so the most negative integer is reachable.
+            Node::Neg(inner) => match &**inner {
+                Node::Literal { text, column } => {
+                    self.parse_literal(&format!("-{}", text), *column)
+                }
+                _ => self.track(ops::neg(&self.eval(inner)?, self.overflow)),
+            },
+            Node::Binary { op, lhs, rhs } => {
+                let lhs = self.eval(lhs)?;
+                let rhs = self.eval(rhs)?;
+                self.track(ops::apply(*op, &lhs, &rhs, self.overflow, self.rounding))
+            }
+        }
+    }
+
+    fn track(&self, result: Result<(T, bool), CalcError>) -> Result<T, CalcError> {
+        let (value, overflowed) = result?;
+        if overflowed {
+            self.overflowed.set(true);
+        }
+        Ok(value)
+    }
+
+    fn parse_literal(&self, text: &str, column: usize) -> Result<T, CalcError> {
+        radix::parse(text).map_err(|reason| {
+            syntax_error(
+                self.source,
+                column,
+                &format!("`{}` is not a valid {}: {}", text, T::NAME, reason),
+            )
+        })
+    }
+}
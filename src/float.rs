@@ -0,0 +1,137 @@
+//! IEEE-754 introspection for the binary floating-point types.
+
+use std::num::FpCategory;
+
+use crate::bignum::BigInt;
+use crate::decimal::Decimal;
+use crate::number::Number;
+
+/// A binary floating-point type whose encoding can be taken apart.
+pub trait Float: Number + Copy {
+    /// Width of the biased exponent field.
+    const EXPONENT_BITS: u32;
+    /// Width of the stored fraction, without the implicit leading bit.
+    const MANTISSA_BITS: u32;
+
+    /// The encoding, zero-extended to 64 bits.
+    fn to_raw(self) -> u64;
+    fn category(self) -> FpCategory;
+
+    /// The gap between `|self|` and the next representable magnitude, or
+    /// `None` for NaN and infinities.
+    fn ulp(self) -> Option<Self>;
+}
+
+macro_rules! impl_float {
+    ($($ty:ident: $exponent:expr, $mantissa:expr;)*) => {
+        $(
+            impl Float for $ty {
+                const EXPONENT_BITS: u32 = $exponent;
+                const MANTISSA_BITS: u32 = $mantissa;
+
+                fn to_raw(self) -> u64 {
+                    u64::from(self.to_bits())
+                }
+
+                fn category(self) -> FpCategory {
+                    self.classify()
+                }
+
+                fn ulp(self) -> Option<Self> {
+                    if !self.is_finite() {
+                        return None;
+                    }
+                    let magnitude = self.abs();
+                    if magnitude == $ty::MAX {
+                        Some(magnitude - magnitude.next_down())
+                    } else {
+                        Some(magnitude.next_up() - magnitude)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_float! {
+    f32: 8, 23;
+    f64: 11, 52;
+}
+
+/// The fields of a float's encoding.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Parts {
+    pub negative: bool,
+    /// The biased exponent field.
+    pub exponent: u64,
+    /// The stored fraction field.
+    pub mantissa: u64,
+}
+
+impl Parts {
+    pub fn of<F: Float>(value: F) -> Parts {
+        let raw = value.to_raw();
+        Parts {
+            negative: raw >> (F::EXPONENT_BITS + F::MANTISSA_BITS) & 1 == 1,
+            exponent: raw >> F::MANTISSA_BITS & ((1 << F::EXPONENT_BITS) - 1),
+            mantissa: raw & ((1 << F::MANTISSA_BITS) - 1),
+        }
+    }
+}
+
+pub fn bias<F: Float>() -> i64 {
+    (1 << (F::EXPONENT_BITS - 1)) - 1
+}
+
+/// The exact value of a finite float as a decimal; every binary fraction has
+/// a terminating decimal expansion.
+pub fn exact<F: Float>(value: F) -> Option<Decimal> {
+    let parts = Parts::of(value);
+    if parts.exponent == (1 << F::EXPONENT_BITS) - 1 {
+        return None;
+    }
+    // value = significand * 2^power
+    let (significand, power) = if parts.exponent == 0 {
+        (
+            parts.mantissa,
+            1 - bias::<F>() - i64::from(F::MANTISSA_BITS),
+        )
+    } else {
+        (
+            parts.mantissa | 1 << F::MANTISSA_BITS,
+            parts.exponent as i64 - bias::<F>() - i64::from(F::MANTISSA_BITS),
+        )
+    };
+    let mut significand = BigInt::from(significand as i64);
+    if parts.negative {
+        significand = -&significand;
+    }
+    let exact = if power >= 0 {
+        Decimal::new(&significand * &BigInt::from(2i64).pow(power as u32), 0)
+    } else {
+        // m * 2^-k = m * 5^k / 10^k
+        let scale = (-power) as u32;
+        Decimal::new(&significand * &BigInt::from(5i64).pow(scale), scale)
+    };
+    Some(exact.normalize())
+}
+
+/// How many representable values apart `a` and `b` are, or `None` if
+/// either is NaN. Zero and negative zero are the same place.
+pub fn ulp_distance<F: Float>(a: F, b: F) -> Option<u128> {
+    if a.category() == FpCategory::Nan || b.category() == FpCategory::Nan {
+        return None;
+    }
+    Some(ordinal(a).abs_diff(ordinal(b)))
+}
+
+/// Position of a float on the number line of representable values.
+fn ordinal<F: Float>(value: F) -> i128 {
+    let sign = 1u64 << (F::EXPONENT_BITS + F::MANTISSA_BITS);
+    let raw = value.to_raw();
+    if raw & sign != 0 {
+        -i128::from(raw & !sign)
+    } else {
+        i128::from(raw)
+    }
+}
@@ -0,0 +1,137 @@
+//! The commands and options of the command line, as a tree.
+//!
+//! argh does not expose the commands and options it parses, so they are
+//! read back from the `--help` output it generates for every command. This
+//! keeps completions and the reference docs in step with `DemoCli` without
+//! a second list of them.
+
+use argh::FromArgs;
+
+use crate::cli::DemoCli;
+
+/// The name the binary is installed as.
+pub const PROGRAM: &str = "argh-demo";
+
+/// An option of a command, such as `--type`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct OptionInfo {
+    /// The option with its dashes.
+    pub long: String,
+    pub description: String,
+    /// Whether a value follows the option, as opposed to a switch.
+    pub takes_value: bool,
+}
+
+/// A command and everything below it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Command {
+    /// The names from the program down to this command, such as
+    /// `["argh-demo", "stats", "mean"]`.
+    pub path: Vec<String>,
+    /// The usage line, such as `argh-demo stats mean [<operands...>]`.
+    pub synopsis: String,
+    pub description: String,
+    pub options: Vec<OptionInfo>,
+    pub subcommands: Vec<Command>,
+}
+
+impl Command {
+    pub fn name(&self) -> &str {
+        self.path.last().expect("paths start with the program")
+    }
+
+    /// The path joined with `;`, which identifies the command in scripts.
+    pub fn key(&self) -> String {
+        self.path.join(";")
+    }
+
+    /// This command and every command below it, parents first.
+    pub fn walk(&self) -> Vec<&Command> {
+        let mut commands = vec![self];
+        for subcommand in &self.subcommands {
+            commands.extend(subcommand.walk());
+        }
+        commands
+    }
+}
+
+/// Every command of `DemoCli`, read from its help output.
+pub fn command_tree() -> Command {
+    command(vec![PROGRAM.to_string()])
+}
+
+fn command(path: Vec<String>) -> Command {
+    let mut args: Vec<&str> = path[1..].iter().map(String::as_str).collect();
+    args.push("--help");
+    let help = match DemoCli::from_args(&[PROGRAM], &args) {
+        Err(exit) => exit.output,
+        Ok(_) => unreachable!("--help always exits early"),
+    };
+    let mut lines = help.lines();
+    let usage = lines.next().unwrap_or_default();
+    let synopsis = usage.trim_start_matches("Usage: ").to_string();
+    let description = lines
+        .clone()
+        .skip_while(|line| line.is_empty())
+        .take_while(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut options = Vec::new();
+    let mut subcommands = Vec::new();
+    while let Some(line) = lines.next() {
+        let entries = section(lines.clone());
+        match line {
+            "Options:" => {
+                options = entries
+                    .into_iter()
+                    .map(|(long, description)| OptionInfo {
+                        takes_value: usage.contains(&format!("{} <", long)),
+                        long,
+                        description,
+                    })
+                    .collect();
+            }
+            "Commands:" => {
+                subcommands = entries
+                    .into_iter()
+                    .map(|(name, _)| {
+                        let mut path = path.clone();
+                        path.push(name);
+                        command(path)
+                    })
+                    .collect();
+            }
+            _ => continue,
+        }
+    }
+    Command {
+        path,
+        synopsis,
+        description,
+        options,
+        subcommands,
+    }
+}
+
+/// Reads the `  name    description` entries of a help section up to the
+/// first blank line, joining wrapped descriptions.
+fn section<'a>(lines: impl Iterator<Item = &'a str>) -> Vec<(String, String)> {
+    let mut entries: Vec<(String, String)> = Vec::new();
+    for line in lines.take_while(|line| !line.trim().is_empty()) {
+        let text = line.trim();
+        match entries.last_mut() {
+            // Continuations are indented past the names.
+            Some((_, description)) if line.starts_with("   ") => {
+                if !description.is_empty() {
+                    description.push(' ');
+                }
+                description.push_str(text);
+            }
+            _ => {
+                let (name, description) = text.split_once("  ").unwrap_or((text, ""));
+                entries.push((name.to_string(), description.trim().to_string()));
+            }
+        }
+    }
+    entries
+}
@@ -0,0 +1,250 @@
+//! The history of calculations: every record printed is kept, with when it
+//! was made and the command line that made it, so that results can be
+//! audited and reused.
+//!
+//! The history is `$XDG_DATA_HOME/argh-demo/calculations`, one entry per
+//! line. Fields are separated by tabs and lists by U+001F, with tabs,
+//! newlines and backslashes escaped. Entries are numbered from 1 in the
+//! order they were made.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::date::{Instant, Offset};
+use crate::dirs;
+use crate::error::CalcError;
+use crate::help::PROGRAM;
+use crate::output::Record;
+
+const LIST_SEPARATOR: char = '\u{1f}';
+
+/// A calculation in the history.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Entry {
+    /// The 1-based number of the entry.
+    pub id: usize,
+    /// Seconds since 1970-01-01T00:00:00Z.
+    pub timestamp: i64,
+    pub operation: String,
+    /// The operands, or `None` when they were streamed from input.
+    pub operands: Option<Vec<String>>,
+    pub result: String,
+    pub ty: String,
+    pub overflowed: bool,
+    /// The calculation as plain output shows it, such as `1 + 2 = 3`.
+    pub text: String,
+    /// The arguments of the command line that made the entry.
+    pub args: Vec<String>,
+}
+
+impl Entry {
+    /// An entry for `record`, made now; its id is set when it is read back.
+    pub fn new(record: &Record, text: String, args: &[String]) -> Entry {
+        Entry {
+            id: 0,
+            timestamp: Instant::now().timestamp(),
+            operation: record.operation.to_string(),
+            operands: record.operands.clone(),
+            result: record.result.clone(),
+            ty: record.ty.to_string(),
+            overflowed: record.overflowed,
+            text,
+            args: args.to_vec(),
+        }
+    }
+
+    /// When the entry was made, in UTC.
+    pub fn time(&self) -> Instant {
+        Instant::from_timestamp(self.timestamp, Offset::default())
+            .expect("timestamps are checked when entries are made or read")
+    }
+
+    /// The command line that made the entry, as it could be typed in a
+    /// shell.
+    pub fn command(&self) -> String {
+        let mut words = vec![PROGRAM.to_string()];
+        words.extend(self.args.iter().map(|arg| {
+            let plain = !arg.is_empty()
+                && arg
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || "-_.,:/+=@%^".contains(c));
+            if plain {
+                arg.clone()
+            } else {
+                format!("'{}'", arg.replace('\'', "'\\''"))
+            }
+        }));
+        words.join(" ")
+    }
+
+    fn to_line(&self) -> String {
+        let operands = match &self.operands {
+            Some(operands) => join(operands),
+            None => String::new(),
+        };
+        let fields = [
+            self.timestamp.to_string(),
+            escape(&self.operation),
+            operands,
+            escape(&self.result),
+            escape(&self.ty),
+            self.overflowed.to_string(),
+            escape(&self.text),
+            join(&self.args),
+        ];
+        fields.join("\t")
+    }
+
+    fn parse(id: usize, line: &str) -> Option<Entry> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [timestamp, operation, operands, result, ty, overflowed, text, args] = fields[..]
+        else {
+            return None;
+        };
+        Some(Entry {
+            id,
+            timestamp: timestamp.parse().ok().filter(|&timestamp| {
+                Instant::from_timestamp(timestamp, Offset::default()).is_some()
+            })?,
+            operation: unescape(operation),
+            operands: if operands.is_empty() {
+                None
+            } else {
+                Some(split(operands))
+            },
+            result: unescape(result),
+            ty: unescape(ty),
+            overflowed: overflowed.parse().ok()?,
+            text: unescape(text),
+            args: if args.is_empty() {
+                Vec::new()
+            } else {
+                split(args)
+            },
+        })
+    }
+}
+
+/// The history file of this user.
+#[derive(Clone, PartialEq, Debug)]
+pub struct History {
+    path: PathBuf,
+}
+
+impl History {
+    /// The history in the user's data directory.
+    pub fn open() -> Result<History, CalcError> {
+        let dir = dirs::data_dir().ok_or_else(|| {
+            CalcError::Usage("the history needs $HOME or $XDG_DATA_HOME to be set".to_string())
+        })?;
+        Ok(History {
+            path: dir.join("calculations"),
+        })
+    }
+
+    /// Every entry, oldest first. Lines that cannot be read, such as one
+    /// cut short by a full disk, are skipped but still numbered.
+    pub fn entries(&self) -> Result<Vec<Entry>, CalcError> {
+        let contents = match fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(self.error("read", err)),
+        };
+        Ok(contents
+            .lines()
+            .enumerate()
+            .filter_map(|(index, line)| Entry::parse(index + 1, line))
+            .collect())
+    }
+
+    /// The entry numbered `id`.
+    pub fn entry(&self, id: usize) -> Result<Entry, CalcError> {
+        self.entries()?
+            .into_iter()
+            .find(|entry| entry.id == id)
+            .ok_or_else(|| CalcError::Usage(format!("there is no history entry {}", id)))
+    }
+
+    /// Adds `entries` at the end.
+    pub fn append(&self, entries: &[Entry]) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let lines: String = entries
+            .iter()
+            .map(|entry| format!("{}\n", entry.to_line()))
+            .collect();
+        // One write for all, so that concurrent runs do not interleave.
+        file.write_all(lines.as_bytes())
+    }
+
+    /// Forgets every entry.
+    pub fn clear(&self) -> Result<(), CalcError> {
+        match fs::remove_file(&self.path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(self.error("write", err)),
+            _ => Ok(()),
+        }
+    }
+
+    fn error(&self, action: &'static str, err: io::Error) -> CalcError {
+        CalcError::Io {
+            path: self.path.display().to_string(),
+            action,
+            message: err.to_string(),
+        }
+    }
+}
+
+/// The result of history entry `id`, for reuse as an operand.
+pub fn recall(id: usize) -> Result<String, CalcError> {
+    Ok(History::open()?.entry(id)?.result)
+}
+
+fn join(items: &[String]) -> String {
+    let escaped: Vec<String> = items.iter().map(|item| escape(item)).collect();
+    escaped.join(&LIST_SEPARATOR.to_string())
+}
+
+fn split(field: &str) -> Vec<String> {
+    field.split(LIST_SEPARATOR).map(unescape).collect()
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            LIST_SEPARATOR => out.push_str("\\u"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('u') => out.push(LIST_SEPARATOR),
+            Some(c) => out.push(c),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
@@ -0,0 +1,151 @@
+//! Streams of operands read from files or stdin.
+//!
+//! Values may be separated by newlines, other whitespace or commas. Input is
+//! read one line at a time, so arbitrarily long streams use constant memory.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::marker::PhantomData;
+
+use crate::error::CalcError;
+use crate::number::Number;
+use crate::radix;
+
+/// A file or stdin that operands are read from.
+pub struct Input {
+    name: String,
+    reader: Box<dyn BufRead>,
+}
+
+impl Input {
+    /// Opens `path`, where `-` means stdin.
+    pub fn open(path: &str) -> Result<Input, CalcError> {
+        if path == "-" {
+            return Ok(Input::stdin());
+        }
+        let file = File::open(path).map_err(|err| CalcError::Io {
+            path: path.to_string(),
+            action: "read",
+            message: err.to_string(),
+        })?;
+        Ok(Input {
+            name: path.to_string(),
+            reader: Box::new(BufReader::new(file)),
+        })
+    }
+
+    pub fn stdin() -> Input {
+        Input {
+            name: "<stdin>".to_string(),
+            reader: Box::new(BufReader::new(io::stdin())),
+        }
+    }
+
+    /// The name used in messages: the path, or `<stdin>`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The lines of the input, without their line endings.
+    pub fn lines(self) -> impl Iterator<Item = Result<String, CalcError>> {
+        let Input { name, reader } = self;
+        reader.lines().map(move |line| {
+            line.map_err(|err| CalcError::Io {
+                path: name.clone(),
+                action: "read",
+                message: err.to_string(),
+            })
+        })
+    }
+
+    /// Parses every token in the input as `T`.
+    pub fn values<T: Number>(self) -> Values<T> {
+        Values {
+            input: self,
+            line: String::new(),
+            line_number: 0,
+            pending: VecDeque::new(),
+            done: false,
+            ty: PhantomData,
+        }
+    }
+}
+
+/// Opens each of `files`, then stdin if `stdin` is set, in that order.
+pub fn open_all(files: &[String], stdin: bool) -> Result<Vec<Input>, CalcError> {
+    let mut inputs = files
+        .iter()
+        .map(|path| Input::open(path))
+        .collect::<Result<Vec<_>, _>>()?;
+    if stdin {
+        inputs.push(Input::stdin());
+    }
+    Ok(inputs)
+}
+
+/// The values of an `Input`, produced lazily.
+pub struct Values<T> {
+    input: Input,
+    line: String,
+    line_number: usize,
+    pending: VecDeque<String>,
+    done: bool,
+    ty: PhantomData<T>,
+}
+
+impl<T: Number> Values<T> {
+    fn next_token(&mut self) -> Result<Option<String>, CalcError> {
+        while self.pending.is_empty() {
+            if self.done {
+                return Ok(None);
+            }
+            self.line.clear();
+            let read =
+                self.input
+                    .reader
+                    .read_line(&mut self.line)
+                    .map_err(|err| CalcError::Io {
+                        path: self.input.name.clone(),
+                        action: "read",
+                        message: err.to_string(),
+                    })?;
+            if read == 0 {
+                self.done = true;
+                return Ok(None);
+            }
+            self.line_number += 1;
+            self.pending.extend(
+                self.line
+                    .split(|c: char| c.is_whitespace() || c == ',')
+                    .filter(|token| !token.is_empty())
+                    .map(str::to_string),
+            );
+        }
+        Ok(self.pending.pop_front())
+    }
+}
+
+impl<T: Number> Iterator for Values<T> {
+    type Item = Result<T, CalcError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = match self.next_token() {
+            Ok(token) => token?,
+            Err(err) => {
+                self.done = true;
+                self.pending.clear();
+                return Some(Err(err));
+            }
+        };
+        Some(
+            radix::parse(&token).map_err(|reason| CalcError::InvalidInput {
+                source: self.input.name.clone(),
+                line: self.line_number,
+                text: token,
+                ty: T::NAME,
+                reason,
+            }),
+        )
+    }
+}
@@ -0,0 +1,49 @@
+//! Just a demo for argh: a calculator library and its command line.
+//!
+//! The arithmetic is exposed through [`Calculator`], generic over every
+//! [`Number`] type, so it can be used without going through the binary:
+//!
+//! ```
+//! use argh_demo::{Calculator, Operation, Overflow};
+//!
+//! let calc = Calculator::<u8>::new().overflow(Overflow::Saturating);
+//! let sum = calc.apply(Operation::Add, &200, &100).unwrap();
+//! assert_eq!(sum.value, 255);
+//! assert!(sum.overflowed);
+//! ```
+
+#[macro_use]
+pub mod number;
+
+pub mod bignum;
+pub mod bits;
+pub mod calculator;
+pub mod cli;
+pub mod commands;
+pub mod completions;
+pub mod complex;
+pub mod config;
+pub mod date;
+pub mod decimal;
+pub mod docs;
+pub mod error;
+pub mod expr;
+pub mod float;
+pub mod help;
+pub mod history;
+pub mod input;
+pub mod locale;
+pub mod ops;
+pub mod output;
+pub mod radix;
+pub mod rational;
+pub mod registers;
+pub mod stats;
+pub mod units;
+
+mod dirs;
+
+pub use crate::calculator::{Calculation, Calculator};
+pub use crate::error::CalcError;
+pub use crate::number::{NumType, Number, Rounding};
+pub use crate::ops::{Operation, Overflow};
@@ -0,0 +1,120 @@
+//! How plain output writes the digits of numbers, chosen with `--locale`.
+//!
+//! Only the separators change: digits are always ASCII, and results in
+//! bare, JSON, CSV and TSV output, the history and registers keep `.` and
+//! no grouping, so that they can be read back as operands.
+
+use std::str::FromStr;
+
+/// Separators for writing numbers, by language, for the languages whose
+/// conventions are widely agreed on.
+const LANGUAGES: &[(&[&str], Option<char>, char)] = &[
+    (&["en", "ja", "zh", "ko", "he", "th"], Some(','), '.'),
+    (
+        &["de", "es", "it", "nl", "pt", "da", "id", "tr", "el"],
+        Some('.'),
+        ',',
+    ),
+    (
+        &[
+            "fr", "ru", "pl", "cs", "sk", "sv", "fi", "nb", "no", "uk", "hu",
+        ],
+        Some('\u{a0}'),
+        ',',
+    ),
+];
+
+/// The separators of a locale. The default, `C`, writes numbers as
+/// operands are written, as in `1234.5`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Locale {
+    /// Written between groups of three integer digits, if at all.
+    group: Option<char>,
+    /// Written in place of the decimal point.
+    decimal: char,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale {
+            group: None,
+            decimal: '.',
+        }
+    }
+}
+
+impl Locale {
+    /// Rewrites the numbers in `text`, which are written as operands are,
+    /// such as `-1234.5`, `3/4` or `5∠53.13°`, with this locale's
+    /// separators.
+    pub fn apply(&self, text: &str) -> String {
+        if *self == Locale::default() {
+            return text.to_string();
+        }
+        let chars: Vec<char> = text.chars().collect();
+        let mut out = String::with_capacity(text.len());
+        let mut i = 0;
+        while i < chars.len() {
+            if !chars[i].is_ascii_digit() {
+                out.push(chars[i]);
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            for (index, &digit) in chars[start..i].iter().enumerate() {
+                let left = i - start - index;
+                if let (Some(group), true) = (self.group, index > 0 && left % 3 == 0) {
+                    out.push(group);
+                }
+                out.push(digit);
+            }
+            // A fractional part is not grouped.
+            if chars.get(i) == Some(&'.') && chars.get(i + 1).is_some_and(char::is_ascii_digit) {
+                out.push(self.decimal);
+                i += 1;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    out.push(chars[i]);
+                    i += 1;
+                }
+            }
+        }
+        out
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    /// Reads a locale name such as `de`, `de_DE` or `de_DE.UTF-8`; the
+    /// encoding and any `@modifier` are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.split(['.', '@']).next().unwrap_or_default();
+        if let "C" | "POSIX" = name {
+            return Ok(Locale::default());
+        }
+        let language = name.split(['_', '-']).next().unwrap_or_default();
+        let (group, decimal) = match name.replace('-', "_").as_str() {
+            // The Swiss group with apostrophes and keep the point.
+            "de_CH" | "fr_CH" | "it_CH" => (Some('’'), '.'),
+            _ => LANGUAGES
+                .iter()
+                .find(|(languages, _, _)| languages.contains(&language))
+                .map(|&(_, group, decimal)| (group, decimal))
+                .ok_or_else(|| {
+                    let known: Vec<&str> = LANGUAGES
+                        .iter()
+                        .flat_map(|(languages, _, _)| languages.iter().copied())
+                        .collect();
+                    format!(
+                        "unknown locale `{}`, expected C or a language such as: {}",
+                        s,
+                        known.join(", ")
+                    )
+                })?,
+        };
+        Ok(Locale { group, decimal })
+    }
+}
@@ -1,31 +1,76 @@
 //! Just a demo for argh.
 
-use argh::FromArgs;
+use std::env;
+use std::path::Path;
 
-mod commands;
+use argh::TopLevelCommand;
+use argh_demo::cli::{self, DemoCli};
+use argh_demo::commands::gen_docs::{self, GenDocsOptions};
+use argh_demo::history::History;
+use argh_demo::CalcError;
 
-#[derive(FromArgs)]
-/// A simple calculation tool
-struct DemoCli {
-    #[argh(subcommand)]
-    subcommand: SubCommands,
-}
-
-#[derive(FromArgs, PartialEq, Debug)]
-#[argh(subcommand)]
-enum SubCommands {
-    Add(commands::add::AddOptions),
-    Sub(commands::sub::SubOptions),
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    // `gen-docs` is parsed apart from `DemoCli`, which keeps it out of the
+    // help, completions and docs it generates.
+    let result = if args.get(1).map(String::as_str) == Some("gen-docs") {
+        gen_docs::execute(parse::<GenDocsOptions>(&args[0], &["gen-docs"], &args[2..]))
+    } else {
+        // `!N` re-runs the command line of history entry N.
+        if let Some(id) = args.get(1).and_then(|arg| arg.strip_prefix('!')) {
+            match rerun(id) {
+                Ok(rerun) => {
+                    args.truncate(1);
+                    args.extend(rerun);
+                }
+                Err(err) => exit(err),
+            }
+        }
+        let cli = parse::<DemoCli>(&args[0], &[], &args[1..]);
+        cli::run(cli, &args[1..])
+    };
+    if let Err(err) = result {
+        exit(err);
+    }
 }
 
-fn main() {
-    let cli: DemoCli = argh::from_env();
-    match cli.subcommand {
-        SubCommands::Add(options) => {
-            commands::add::execute(options);
+/// Parses `args` given to `subcommand` of `program`, exiting on `--help` or
+/// errors. Like `argh::from_env`, usage names the program by its file name;
+/// errors go to stderr with a hint to ask for help.
+fn parse<T: TopLevelCommand>(program: &str, subcommand: &[&str], args: &[String]) -> T {
+    let name = Path::new(program)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(program);
+    let mut command = vec![name];
+    command.extend_from_slice(subcommand);
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    T::from_args(&command, &args).unwrap_or_else(|exit| match exit.status {
+        Ok(()) => {
+            println!("{}", exit.output);
+            std::process::exit(0)
         }
-        SubCommands::Sub(options) => {
-            commands::sub::execute(options);
+        Err(()) => {
+            eprintln!(
+                "{}\nRun {} --help for more information.",
+                exit.output.trim_end(),
+                command.join(" ")
+            );
+            std::process::exit(1)
         }
-    };
+    })
+}
+
+fn rerun(id: &str) -> Result<Vec<String>, CalcError> {
+    let id = id
+        .parse()
+        .map_err(|_| CalcError::Usage(format!("`!{}` is not a history entry", id)))?;
+    let entry = History::open()?.entry(id)?;
+    eprintln!("{}", entry.command());
+    Ok(entry.args)
+}
+
+fn exit(err: CalcError) -> ! {
+    eprintln!("error: {}", err);
+    std::process::exit(err.exit_code());
 }
@@ -0,0 +1,802 @@
+//! Numeric types the calculator can operate on.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::bignum::BigInt;
+use crate::complex::Complex;
+use crate::decimal::{Decimal, RoundingMode, MAX_SCALE};
+use crate::float;
+use crate::ops::Overflow;
+use crate::rational::Rational;
+
+/// Why an arithmetic operation has no result in the operand type.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ArithError {
+    /// The exact result does not fit in the type.
+    Overflow,
+    /// The divisor of a division or remainder is zero.
+    DivisionByZero,
+    /// An integer type was raised to a negative power.
+    NegativeExponent,
+    /// The operation is not defined for these operands.
+    Domain(&'static str),
+}
+
+/// A value type the arithmetic in `ops` is written against.
+///
+/// Each operation honours `overflow` when the exact result does not fit.
+pub trait Number: Clone + fmt::Display {
+    /// Name of the type, as accepted by `--type`.
+    const NAME: &'static str;
+
+    /// Parses an operand given on the command line.
+    fn parse(text: &str) -> Result<Self, String>;
+
+    fn zero() -> Self;
+
+    fn add(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError>;
+    fn sub(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError>;
+    fn mul(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError>;
+
+    /// Divides; integers truncate toward zero and decimals keep the digits
+    /// requested by `rounding`.
+    fn div(&self, rhs: &Self, overflow: Overflow, rounding: &Rounding) -> Result<Self, ArithError>;
+
+    /// Remainder of the truncating division, with the sign of `self`.
+    fn rem(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError>;
+
+    /// Euclidean remainder, which is never negative.
+    fn rem_euclid(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError>;
+
+    fn pow(&self, exp: &Self, overflow: Overflow) -> Result<Self, ArithError>;
+
+    fn neg(&self, overflow: Overflow) -> Result<Self, ArithError> {
+        Self::zero().sub(self, overflow)
+    }
+
+    /// Drops the fractional part, rounding toward zero.
+    fn trunc(&self) -> Self {
+        self.clone()
+    }
+
+    /// Applies `--scale`/`--rounding` to a result. Only decimals are affected.
+    fn round(self, _rounding: &Rounding) -> Self {
+        self
+    }
+
+    /// The exact value as a fraction, or `None` for NaN and infinities.
+    fn exact(&self) -> Option<Rational> {
+        self.to_string().parse().ok()
+    }
+}
+
+/// The `--scale` and `--rounding` settings for decimal results.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Rounding {
+    /// Number of fractional digits to keep, or `None` to keep sums,
+    /// differences and products exact and give quotients up to
+    /// `decimal::DEFAULT_DIV_SCALE` digits.
+    pub scale: Option<u32>,
+    pub mode: RoundingMode,
+}
+
+/// Picks the checked, wrapping or saturating form of an integer method.
+macro_rules! by_overflow {
+    ($overflow:expr, $checked:expr, $wrapping:expr, $saturating:expr) => {
+        match $overflow {
+            Overflow::Checked => $checked.ok_or(ArithError::Overflow),
+            Overflow::Wrapping => Ok($wrapping),
+            Overflow::Saturating => Ok($saturating),
+        }
+    };
+}
+
+macro_rules! impl_number_for_integers {
+    ($($ty:ident,)*) => {
+        $(
+            impl Number for $ty {
+                const NAME: &'static str = stringify!($ty);
+
+                fn parse(text: &str) -> Result<Self, String> {
+                    text.parse().map_err(|err| format!("{}", err))
+                }
+
+                fn zero() -> Self {
+                    0
+                }
+
+                fn add(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError> {
+                    by_overflow!(
+                        overflow,
+                        $ty::checked_add(*self, *rhs),
+                        $ty::wrapping_add(*self, *rhs),
+                        $ty::saturating_add(*self, *rhs)
+                    )
+                }
+
+                fn sub(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError> {
+                    by_overflow!(
+                        overflow,
+                        $ty::checked_sub(*self, *rhs),
+                        $ty::wrapping_sub(*self, *rhs),
+                        $ty::saturating_sub(*self, *rhs)
+                    )
+                }
+
+                fn mul(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError> {
+                    by_overflow!(
+                        overflow,
+                        $ty::checked_mul(*self, *rhs),
+                        $ty::wrapping_mul(*self, *rhs),
+                        $ty::saturating_mul(*self, *rhs)
+                    )
+                }
+
+                fn div(
+                    &self,
+                    rhs: &Self,
+                    overflow: Overflow,
+                    _rounding: &Rounding,
+                ) -> Result<Self, ArithError> {
+                    if *rhs == 0 {
+                        return Err(ArithError::DivisionByZero);
+                    }
+                    by_overflow!(
+                        overflow,
+                        $ty::checked_div(*self, *rhs),
+                        $ty::wrapping_div(*self, *rhs),
+                        $ty::saturating_div(*self, *rhs)
+                    )
+                }
+
+                fn rem(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError> {
+                    if *rhs == 0 {
+                        return Err(ArithError::DivisionByZero);
+                    }
+                    // Only `MIN % -1` overflows, and its exact result is 0.
+                    by_overflow!(
+                        overflow,
+                        $ty::checked_rem(*self, *rhs),
+                        $ty::wrapping_rem(*self, *rhs),
+                        $ty::wrapping_rem(*self, *rhs)
+                    )
+                }
+
+                fn rem_euclid(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError> {
+                    if *rhs == 0 {
+                        return Err(ArithError::DivisionByZero);
+                    }
+                    by_overflow!(
+                        overflow,
+                        $ty::checked_rem_euclid(*self, *rhs),
+                        $ty::wrapping_rem_euclid(*self, *rhs),
+                        $ty::wrapping_rem_euclid(*self, *rhs)
+                    )
+                }
+
+                #[allow(unused_comparisons)]
+                fn pow(&self, exp: &Self, overflow: Overflow) -> Result<Self, ArithError> {
+                    if *exp < 0 {
+                        return Err(ArithError::NegativeExponent);
+                    }
+                    let exp = u32::try_from(*exp)
+                        .map_err(|_| ArithError::Domain("exponent is too large"))?;
+                    by_overflow!(
+                        overflow,
+                        $ty::checked_pow(*self, exp),
+                        $ty::wrapping_pow(*self, exp),
+                        $ty::saturating_pow(*self, exp)
+                    )
+                }
+            }
+        )*
+    }
+}
+
+impl_number_for_integers! {
+    i8, i16, i32, i64, i128,
+    u8, u16, u32, u64, u128,
+}
+
+// Floats never wrap: overflow means finite operands produced an infinity,
+// which checked mode reports and saturating mode clamps to the finite range.
+// Wrapping mode keeps IEEE 754 results, so there dividing by zero gives an
+// infinity or NaN rather than an error.
+macro_rules! impl_number_for_floats {
+    ($($ty:ident,)*) => {
+        $(
+            impl Number for $ty {
+                const NAME: &'static str = stringify!($ty);
+
+                fn parse(text: &str) -> Result<Self, String> {
+                    text.parse().map_err(|err| format!("{}", err))
+                }
+
+                fn zero() -> Self {
+                    0.0
+                }
+
+                fn add(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError> {
+                    float_result(overflow, *self, *rhs, self + rhs)
+                }
+
+                fn sub(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError> {
+                    float_result(overflow, *self, *rhs, self - rhs)
+                }
+
+                fn mul(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError> {
+                    float_result(overflow, *self, *rhs, self * rhs)
+                }
+
+                fn div(
+                    &self,
+                    rhs: &Self,
+                    overflow: Overflow,
+                    _rounding: &Rounding,
+                ) -> Result<Self, ArithError> {
+                    if *rhs == 0.0 && overflow != Overflow::Wrapping {
+                        return Err(ArithError::DivisionByZero);
+                    }
+                    float_result(overflow, *self, *rhs, self / rhs)
+                }
+
+                fn rem(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError> {
+                    if *rhs == 0.0 && overflow != Overflow::Wrapping {
+                        return Err(ArithError::DivisionByZero);
+                    }
+                    float_result(overflow, *self, *rhs, self % rhs)
+                }
+
+                fn rem_euclid(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError> {
+                    if *rhs == 0.0 && overflow != Overflow::Wrapping {
+                        return Err(ArithError::DivisionByZero);
+                    }
+                    float_result(overflow, *self, *rhs, $ty::rem_euclid(*self, *rhs))
+                }
+
+                fn pow(&self, exp: &Self, overflow: Overflow) -> Result<Self, ArithError> {
+                    if *self == 0.0 && *exp < 0.0 && overflow != Overflow::Wrapping {
+                        return Err(ArithError::DivisionByZero);
+                    }
+                    float_result(overflow, *self, *exp, self.powf(*exp))
+                }
+
+                fn neg(&self, _overflow: Overflow) -> Result<Self, ArithError> {
+                    Ok(-self)
+                }
+
+                fn trunc(&self) -> Self {
+                    $ty::trunc(*self)
+                }
+
+                // Display rounds to the shortest text that parses back, which
+                // is not the stored value.
+                fn exact(&self) -> Option<Rational> {
+                    float::exact(*self).map(Rational::from)
+                }
+            }
+        )*
+    }
+}
+
+impl_number_for_floats! {
+    f32, f64,
+}
+
+fn float_result<F>(overflow: Overflow, lhs: F, rhs: F, result: F) -> Result<F, ArithError>
+where
+    F: Into<f64> + Copy + FloatBounds,
+{
+    let (lhs_wide, rhs_wide, out) = (lhs.into(), rhs.into(), result.into());
+    if !out.is_infinite() || !lhs_wide.is_finite() || !rhs_wide.is_finite() {
+        return Ok(result);
+    }
+    match overflow {
+        Overflow::Checked => Err(ArithError::Overflow),
+        Overflow::Wrapping => Ok(result),
+        Overflow::Saturating if out > 0.0 => Ok(F::MAX),
+        Overflow::Saturating => Ok(F::MIN),
+    }
+}
+
+/// The finite range of a float type, for saturating results.
+trait FloatBounds {
+    const MAX: Self;
+    const MIN: Self;
+}
+
+impl FloatBounds for f32 {
+    const MAX: Self = f32::MAX;
+    const MIN: Self = f32::MIN;
+}
+
+impl FloatBounds for f64 {
+    const MAX: Self = f64::MAX;
+    const MIN: Self = f64::MIN;
+}
+
+// Arbitrary-precision values cannot overflow, so every mode gives the exact
+// result.
+impl Number for BigInt {
+    const NAME: &'static str = "big";
+
+    fn parse(text: &str) -> Result<Self, String> {
+        text.parse()
+    }
+
+    fn zero() -> Self {
+        BigInt::zero()
+    }
+
+    fn add(&self, rhs: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        Ok(self + rhs)
+    }
+
+    fn sub(&self, rhs: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        Ok(self - rhs)
+    }
+
+    fn mul(&self, rhs: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        Ok(self * rhs)
+    }
+
+    fn div(
+        &self,
+        rhs: &Self,
+        _overflow: Overflow,
+        _rounding: &Rounding,
+    ) -> Result<Self, ArithError> {
+        let (quotient, _) = self.div_rem(rhs).ok_or(ArithError::DivisionByZero)?;
+        Ok(quotient)
+    }
+
+    fn rem(&self, rhs: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        let (_, remainder) = self.div_rem(rhs).ok_or(ArithError::DivisionByZero)?;
+        Ok(remainder)
+    }
+
+    fn rem_euclid(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError> {
+        let remainder = Number::rem(self, rhs, overflow)?;
+        if remainder.is_negative() {
+            Ok(&remainder + &rhs.abs())
+        } else {
+            Ok(remainder)
+        }
+    }
+
+    fn pow(&self, exp: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        let exp = big_exponent(exp)?;
+        check_power(self, 0, exp)?;
+        Ok(BigInt::pow(self, exp))
+    }
+}
+
+impl Number for Decimal {
+    const NAME: &'static str = "decimal";
+
+    fn parse(text: &str) -> Result<Self, String> {
+        text.parse()
+    }
+
+    fn zero() -> Self {
+        Decimal::new(BigInt::zero(), 0)
+    }
+
+    fn add(&self, rhs: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        Ok(Decimal::add(self, rhs))
+    }
+
+    fn sub(&self, rhs: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        Ok(Decimal::sub(self, rhs))
+    }
+
+    fn mul(&self, rhs: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        Ok(Decimal::mul(self, rhs))
+    }
+
+    fn div(
+        &self,
+        rhs: &Self,
+        _overflow: Overflow,
+        rounding: &Rounding,
+    ) -> Result<Self, ArithError> {
+        Decimal::div(self, rhs, rounding.scale, rounding.mode).ok_or(ArithError::DivisionByZero)
+    }
+
+    fn rem(&self, rhs: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        Decimal::rem(self, rhs).ok_or(ArithError::DivisionByZero)
+    }
+
+    fn rem_euclid(&self, rhs: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        let remainder = Decimal::rem(self, rhs).ok_or(ArithError::DivisionByZero)?;
+        if remainder.is_negative() {
+            Ok(Decimal::add(&remainder, &rhs.abs()))
+        } else {
+            Ok(remainder)
+        }
+    }
+
+    fn pow(&self, exp: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        let exp = exp
+            .to_integer()
+            .ok_or(ArithError::Domain("exponent must be an integer"))?;
+        let exp = big_exponent(&exp)?;
+        let (unscaled, scale) = self.parts();
+        check_power(unscaled, scale, exp)?;
+        Decimal::pow(self, exp).ok_or(ArithError::Domain("exponent is too large"))
+    }
+
+    fn trunc(&self) -> Self {
+        Decimal::round(self, 0, RoundingMode::Truncate)
+    }
+
+    fn round(self, rounding: &Rounding) -> Self {
+        match rounding.scale {
+            Some(scale) => Decimal::round(&self, scale, rounding.mode),
+            None => self,
+        }
+    }
+}
+
+// Fractions are exact and unbounded, so like `BigInt` they ignore the overflow
+// mode.
+impl Number for Rational {
+    const NAME: &'static str = "rational";
+
+    fn parse(text: &str) -> Result<Self, String> {
+        text.parse()
+    }
+
+    fn zero() -> Self {
+        Rational::zero()
+    }
+
+    fn add(&self, rhs: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        Ok(Rational::add(self, rhs))
+    }
+
+    fn sub(&self, rhs: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        Ok(Rational::sub(self, rhs))
+    }
+
+    fn mul(&self, rhs: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        Ok(Rational::mul(self, rhs))
+    }
+
+    fn div(
+        &self,
+        rhs: &Self,
+        _overflow: Overflow,
+        _rounding: &Rounding,
+    ) -> Result<Self, ArithError> {
+        Rational::div(self, rhs).ok_or(ArithError::DivisionByZero)
+    }
+
+    fn rem(&self, rhs: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        Rational::rem(self, rhs).ok_or(ArithError::DivisionByZero)
+    }
+
+    fn rem_euclid(&self, rhs: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        let remainder = Rational::rem(self, rhs).ok_or(ArithError::DivisionByZero)?;
+        if remainder.is_negative() {
+            Ok(Rational::add(&remainder, &rhs.abs()))
+        } else {
+            Ok(remainder)
+        }
+    }
+
+    fn pow(&self, exp: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        if !exp.is_integer() {
+            return Err(ArithError::Domain("exponent must be an integer"));
+        }
+        let exp = exp
+            .numer()
+            .to_i128()
+            .and_then(|exp| i64::try_from(exp).ok())
+            .filter(|exp| exp.unsigned_abs() <= u64::from(u32::MAX))
+            .ok_or(ArithError::Domain("exponent is too large"))?;
+        check_power(self.numer(), 0, exp.unsigned_abs() as u32)?;
+        check_power(self.denom(), 0, exp.unsigned_abs() as u32)?;
+        Rational::pow(self, exp).ok_or(ArithError::DivisionByZero)
+    }
+
+    fn neg(&self, _overflow: Overflow) -> Result<Self, ArithError> {
+        Ok(Rational::neg(self))
+    }
+
+    fn trunc(&self) -> Self {
+        Rational::from_integer(Rational::trunc(self))
+    }
+}
+
+// Like floats, complex results only overflow by turning infinite; saturating
+// has no single bound to clamp to, so it keeps the infinity like wrapping.
+impl Number for Complex {
+    const NAME: &'static str = "complex";
+
+    fn parse(text: &str) -> Result<Self, String> {
+        text.parse()
+    }
+
+    fn zero() -> Self {
+        Complex::default()
+    }
+
+    fn add(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError> {
+        complex_result(overflow, self, rhs, *self + *rhs)
+    }
+
+    fn sub(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError> {
+        complex_result(overflow, self, rhs, *self - *rhs)
+    }
+
+    fn mul(&self, rhs: &Self, overflow: Overflow) -> Result<Self, ArithError> {
+        complex_result(overflow, self, rhs, *self * *rhs)
+    }
+
+    fn div(
+        &self,
+        rhs: &Self,
+        overflow: Overflow,
+        _rounding: &Rounding,
+    ) -> Result<Self, ArithError> {
+        let quotient = Complex::checked_div(*self, *rhs).ok_or(ArithError::DivisionByZero)?;
+        complex_result(overflow, self, rhs, quotient)
+    }
+
+    fn rem(&self, _rhs: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        Err(ArithError::Domain("complex numbers have no remainder"))
+    }
+
+    fn rem_euclid(&self, _rhs: &Self, _overflow: Overflow) -> Result<Self, ArithError> {
+        Err(ArithError::Domain("complex numbers have no remainder"))
+    }
+
+    fn pow(&self, exp: &Self, overflow: Overflow) -> Result<Self, ArithError> {
+        let power = Complex::pow(*self, *exp).ok_or(ArithError::DivisionByZero)?;
+        complex_result(overflow, self, exp, power)
+    }
+
+    fn neg(&self, _overflow: Overflow) -> Result<Self, ArithError> {
+        Ok(-*self)
+    }
+
+    fn trunc(&self) -> Self {
+        Complex::new(self.re.trunc(), self.im.trunc())
+    }
+
+    fn exact(&self) -> Option<Rational> {
+        if self.im == 0.0 {
+            self.re.exact()
+        } else {
+            None
+        }
+    }
+}
+
+fn complex_result(
+    overflow: Overflow,
+    lhs: &Complex,
+    rhs: &Complex,
+    result: Complex,
+) -> Result<Complex, ArithError> {
+    if overflow == Overflow::Checked && lhs.is_finite() && rhs.is_finite() && !result.is_finite() {
+        Err(ArithError::Overflow)
+    } else {
+        Ok(result)
+    }
+}
+
+fn big_exponent(exp: &BigInt) -> Result<u32, ArithError> {
+    if exp.is_negative() {
+        return Err(ArithError::NegativeExponent);
+    }
+    exp.to_i128()
+        .and_then(|exp| u32::try_from(exp).ok())
+        .ok_or(ArithError::Domain("exponent is too large"))
+}
+
+/// The most bits an unbounded power may have, about 79,000 decimal digits;
+/// larger ones take minutes to compute and print.
+const MAX_POWER_BITS: u64 = 1 << 18;
+
+/// Checks that `base ^ exp` stays within `MAX_POWER_BITS`, and that the
+/// power of a decimal with `scale` fractional digits, which has `scale * exp`
+/// of them, stays within `decimal::MAX_SCALE`.
+fn check_power(base: &BigInt, scale: u32, exp: u32) -> Result<(), ArithError> {
+    // A power of n bits has at least (n - 1) * exp + 1.
+    let bits = base.bits().saturating_sub(1) * u64::from(exp);
+    if bits < MAX_POWER_BITS && u64::from(scale) * u64::from(exp) <= u64::from(MAX_SCALE) {
+        Ok(())
+    } else {
+        Err(ArithError::Domain("result is too large"))
+    }
+}
+
+/// The numeric domain selected with `--type`.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum NumType {
+    I8,
+    I16,
+    I32,
+    #[default]
+    I64,
+    I128,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    F32,
+    F64,
+    Big,
+    Decimal,
+    Rational,
+    Complex,
+}
+
+impl NumType {
+    pub const ALL: &'static [NumType] = &[
+        NumType::I8,
+        NumType::I16,
+        NumType::I32,
+        NumType::I64,
+        NumType::I128,
+        NumType::U8,
+        NumType::U16,
+        NumType::U32,
+        NumType::U64,
+        NumType::U128,
+        NumType::F32,
+        NumType::F64,
+        NumType::Big,
+        NumType::Decimal,
+        NumType::Rational,
+        NumType::Complex,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            NumType::I8 => i8::NAME,
+            NumType::I16 => i16::NAME,
+            NumType::I32 => i32::NAME,
+            NumType::I64 => i64::NAME,
+            NumType::I128 => i128::NAME,
+            NumType::U8 => u8::NAME,
+            NumType::U16 => u16::NAME,
+            NumType::U32 => u32::NAME,
+            NumType::U64 => u64::NAME,
+            NumType::U128 => u128::NAME,
+            NumType::F32 => f32::NAME,
+            NumType::F64 => f64::NAME,
+            NumType::Big => BigInt::NAME,
+            NumType::Decimal => Decimal::NAME,
+            NumType::Rational => Rational::NAME,
+            NumType::Complex => Complex::NAME,
+        }
+    }
+}
+
+impl fmt::Display for NumType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for NumType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NumType::ALL
+            .iter()
+            .copied()
+            .find(|ty| ty.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = NumType::ALL.iter().map(|ty| ty.name()).collect();
+                format!(
+                    "unknown type `{}`, expected one of: {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// Integer width selected with `--precision`.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Precision {
+    /// Machine integers of the width chosen with `--type`.
+    #[default]
+    Fixed,
+    /// Arbitrary-precision integers.
+    Big,
+}
+
+impl FromStr for Precision {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fixed" => Ok(Precision::Fixed),
+            "big" => Ok(Precision::Big),
+            _ => Err(format!(
+                "unknown precision `{}`, expected one of: fixed, big",
+                s
+            )),
+        }
+    }
+}
+
+/// Runs `$body` with the type alias `$T` bound to the Rust type selected by `$ty`.
+macro_rules! with_number_type {
+    ($ty:expr, $T:ident => $body:expr) => {
+        match $ty {
+            $crate::number::NumType::I8 => {
+                type $T = i8;
+                $body
+            }
+            $crate::number::NumType::I16 => {
+                type $T = i16;
+                $body
+            }
+            $crate::number::NumType::I32 => {
+                type $T = i32;
+                $body
+            }
+            $crate::number::NumType::I64 => {
+                type $T = i64;
+                $body
+            }
+            $crate::number::NumType::I128 => {
+                type $T = i128;
+                $body
+            }
+            $crate::number::NumType::U8 => {
+                type $T = u8;
+                $body
+            }
+            $crate::number::NumType::U16 => {
+                type $T = u16;
+                $body
+            }
+            $crate::number::NumType::U32 => {
+                type $T = u32;
+                $body
+            }
+            $crate::number::NumType::U64 => {
+                type $T = u64;
+                $body
+            }
+            $crate::number::NumType::U128 => {
+                type $T = u128;
+                $body
+            }
+            $crate::number::NumType::F32 => {
+                type $T = f32;
+                $body
+            }
+            $crate::number::NumType::F64 => {
+                type $T = f64;
+                $body
+            }
+            $crate::number::NumType::Big => {
+                type $T = $crate::bignum::BigInt;
+                $body
+            }
+            $crate::number::NumType::Decimal => {
+                type $T = $crate::decimal::Decimal;
+                $body
+            }
+            $crate::number::NumType::Rational => {
+                type $T = $crate::rational::Rational;
+                $body
+            }
+            $crate::number::NumType::Complex => {
+                type $T = $crate::complex::Complex;
+                $body
+            }
+        }
+    };
+}
@@ -0,0 +1,176 @@
+//! Arithmetic shared by every subcommand, written once for all `Number` types.
+
+use std::fmt;
+
+use crate::error::CalcError;
+use crate::number::{ArithError, Number, Rounding};
+use crate::radix;
+
+/// How a result that does not fit in the operand type is handled.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Overflow {
+    /// Report an error.
+    Checked,
+    /// Wrap around at the type's boundary.
+    Wrapping,
+    /// Clamp to the type's minimum or maximum.
+    Saturating,
+}
+
+impl Overflow {
+    /// Picks the mode from a subcommand's `--wrapping`/`--saturating` switches.
+    pub fn from_flags(wrapping: bool, saturating: bool) -> Result<Self, CalcError> {
+        match (wrapping, saturating) {
+            (false, false) => Ok(Overflow::Checked),
+            (true, false) => Ok(Overflow::Wrapping),
+            (false, true) => Ok(Overflow::Saturating),
+            (true, true) => Err(CalcError::Usage(
+                "--wrapping and --saturating cannot be used together".to_string(),
+            )),
+        }
+    }
+}
+
+/// A binary operation the calculator knows how to perform.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Operation {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    /// Euclidean remainder, which is never negative.
+    RemEuclid,
+    Pow,
+}
+
+impl Operation {
+    /// The subcommand-style name used in machine-readable output.
+    pub fn name(self) -> &'static str {
+        match self {
+            Operation::Add => "add",
+            Operation::Sub => "sub",
+            Operation::Mul => "mul",
+            Operation::Div => "div",
+            Operation::Rem => "rem",
+            Operation::RemEuclid => "mod",
+            Operation::Pow => "pow",
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Operation::Add => "+",
+            Operation::Sub => "-",
+            Operation::Mul => "*",
+            Operation::Div => "/",
+            Operation::Rem => "%",
+            Operation::RemEuclid => "mod",
+            Operation::Pow => "^",
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+/// Computes `lhs op rhs`, also reporting whether the result wrapped or
+/// saturated. Any failure becomes a `CalcError` that names the operands.
+pub fn apply<T: Number>(
+    op: Operation,
+    lhs: &T,
+    rhs: &T,
+    overflow: Overflow,
+    rounding: &Rounding,
+) -> Result<(T, bool), CalcError> {
+    let result = tracked(overflow, |overflow| match op {
+        Operation::Add => lhs.add(rhs, overflow),
+        Operation::Sub => lhs.sub(rhs, overflow),
+        Operation::Mul => lhs.mul(rhs, overflow),
+        Operation::Div => lhs.div(rhs, overflow, rounding),
+        Operation::Rem => lhs.rem(rhs, overflow),
+        Operation::RemEuclid => lhs.rem_euclid(rhs, overflow),
+        Operation::Pow => lhs.pow(rhs, overflow),
+    });
+    result.map_err(|err| arith_error(err, op.symbol(), lhs.to_string(), rhs.to_string(), T::NAME))
+}
+
+/// Runs `compute` checked first, so that a wrapped or saturated result can
+/// be flagged, then with the requested mode if the checked form overflowed.
+/// Floats also divide by zero in wrapping mode, giving an infinity or NaN.
+fn tracked<T>(
+    overflow: Overflow,
+    compute: impl Fn(Overflow) -> Result<T, ArithError>,
+) -> Result<(T, bool), ArithError> {
+    match compute(Overflow::Checked) {
+        Err(ArithError::Overflow) if overflow != Overflow::Checked => {
+            compute(overflow).map(|value| (value, true))
+        }
+        Err(ArithError::DivisionByZero) if overflow == Overflow::Wrapping => {
+            compute(overflow).map(|value| (value, true))
+        }
+        result => result.map(|value| (value, false)),
+    }
+}
+
+/// Left-folds `op` over `operands`, so `[a, b, c]` gives `(a op b) op c`.
+/// Operands are consumed one at a time, so streams are never buffered.
+/// Returns `None` when there are no operands, otherwise the result and
+/// whether any step wrapped or saturated.
+pub fn fold<T: Number>(
+    op: Operation,
+    operands: impl IntoIterator<Item = Result<T, CalcError>>,
+    overflow: Overflow,
+    rounding: &Rounding,
+) -> Result<Option<(T, bool)>, CalcError> {
+    let mut acc: Option<(T, bool)> = None;
+    for operand in operands {
+        let operand = operand?;
+        acc = Some(match acc {
+            None => (operand, false),
+            Some((acc, overflowed)) => {
+                let (value, step) = apply(op, &acc, &operand, overflow, rounding)?;
+                (value, overflowed || step)
+            }
+        });
+    }
+    Ok(acc)
+}
+
+/// Computes `-value`, also reporting whether the result wrapped or saturated.
+pub fn neg<T: Number>(value: &T, overflow: Overflow) -> Result<(T, bool), CalcError> {
+    tracked(overflow, |overflow| value.neg(overflow))
+        .map_err(|err| arith_error(err, "-", "0".to_string(), value.to_string(), T::NAME))
+}
+
+/// Parses a command line operand as `T`.
+pub fn parse<T: Number>(text: &str) -> Result<T, CalcError> {
+    radix::parse(text).map_err(|reason| CalcError::InvalidNumber {
+        text: text.to_string(),
+        ty: T::NAME,
+        reason,
+    })
+}
+
+fn arith_error(
+    err: ArithError,
+    op: &'static str,
+    lhs: String,
+    rhs: String,
+    ty: &'static str,
+) -> CalcError {
+    match err {
+        ArithError::Overflow => CalcError::Overflow { op, lhs, rhs, ty },
+        ArithError::DivisionByZero => CalcError::DivisionByZero { op, lhs, rhs },
+        ArithError::NegativeExponent => CalcError::NegativeExponent { exp: rhs, ty },
+        ArithError::Domain(reason) => CalcError::Domain {
+            op,
+            lhs,
+            rhs,
+            reason,
+        },
+    }
+}
@@ -0,0 +1,438 @@
+//! Rendering of calculation results in the format chosen with `--format`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::complex::{Complex, ComplexForm};
+use crate::error::CalcError;
+use crate::history::{Entry, History};
+use crate::locale::Locale;
+use crate::number::Number;
+use crate::ops::Operation;
+use crate::radix::Radix;
+use crate::rational::{FractionStyle, Rational};
+
+/// How results are printed.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Format {
+    /// `1 + 2 = 3`
+    #[default]
+    Plain,
+    /// Only the result.
+    Bare,
+    /// One JSON object per line.
+    Json,
+    /// Comma-separated values, with a header row.
+    Csv,
+    /// Tab-separated values, with a header row.
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Format::Plain),
+            "bare" => Ok(Format::Bare),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(format!(
+                "unknown format `{}`, expected one of: plain, bare, json, csv, tsv",
+                s
+            )),
+        }
+    }
+}
+
+/// One calculation, as reported to the user.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Record {
+    /// Name of the operation, such as `add` or `eval`.
+    pub operation: &'static str,
+    /// Joins the operands in plain output.
+    pub symbol: &'static str,
+    /// The operands, or `None` when they were streamed from input.
+    pub operands: Option<Vec<String>>,
+    pub result: String,
+    /// Name of the numeric type of the result.
+    pub ty: &'static str,
+    /// Whether the result wrapped or saturated.
+    pub overflowed: bool,
+    /// The result minus the exact result, when asked for with
+    /// `--rounding-error`.
+    pub rounding_error: Option<String>,
+}
+
+impl Record {
+    pub fn new<T: Number>(
+        op: Operation,
+        operands: Option<Vec<String>>,
+        result: &T,
+        overflowed: bool,
+    ) -> Record {
+        Record {
+            operation: op.name(),
+            symbol: op.symbol(),
+            operands,
+            result: result.to_string(),
+            ty: T::NAME,
+            overflowed,
+            rounding_error: None,
+        }
+    }
+}
+
+const COLUMNS: [&str; 5] = ["operation", "operands", "result", "type", "overflow"];
+
+/// How numbers are written within a record.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Notation {
+    pub radix: Radix,
+    /// Applies to rational results.
+    pub fractions: FractionStyle,
+    /// Applies to complex results.
+    pub complex: ComplexForm,
+    /// Applies to plain output in base 10.
+    pub locale: Locale,
+}
+
+/// Prints records, writing the CSV/TSV header before the first one.
+#[derive(Debug)]
+pub struct Printer {
+    format: Format,
+    notation: Notation,
+    header_written: bool,
+    /// Whether CSV and TSV rows have a `rounding_error` column.
+    rounding_error_column: bool,
+    /// Where records are kept, with the command line they came from.
+    history: Option<(History, Vec<String>)>,
+    /// Entries not yet saved to the history.
+    unsaved: Vec<Entry>,
+}
+
+impl Printer {
+    pub fn new(format: Format, notation: Notation) -> Self {
+        Printer {
+            format,
+            notation,
+            header_written: false,
+            rounding_error_column: false,
+            history: None,
+            unsaved: Vec::new(),
+        }
+    }
+
+    /// Keeps every record printed from now on in `history`, along with the
+    /// arguments of the command line that made it. Records are saved by
+    /// `save_history`.
+    pub fn keep_history(&mut self, history: History, args: Vec<String>) {
+        self.history = Some((history, args));
+    }
+
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    pub fn radix(&self) -> Radix {
+        self.notation.radix
+    }
+
+    /// Rewrites a value of the type named `ty` in the chosen notation.
+    pub fn format_value(&self, ty: &str, text: &str) -> Result<String, CalcError> {
+        let notation = &self.notation;
+        let formatted = if ty == Rational::NAME {
+            notation.radix.format(&notation.fractions.format(text))
+        } else if ty == Complex::NAME {
+            notation.radix.format(&notation.complex.format(text))
+        } else {
+            notation.radix.format(text)
+        }?;
+        Ok(self.localize(&formatted))
+    }
+
+    /// Rewrites an operand like `format_value`, but leaves it as given
+    /// where the result must be an integer and the operand is not, so that
+    /// `0.5 + 0.5 = 0x1` can still be printed in hexadecimal. Rational
+    /// operands stay fractions: `--as-decimal` and `--mixed` only apply to
+    /// results.
+    fn format_operand(&self, ty: &str, text: &str) -> String {
+        let formatted = if ty == Rational::NAME {
+            self.notation
+                .radix
+                .format(text)
+                .map(|text| self.localize(&text))
+        } else {
+            self.format_value(ty, text)
+        };
+        formatted.unwrap_or_else(|_| text.to_string())
+    }
+
+    /// Writes the numbers in `text` with the separators of `--locale`, in
+    /// plain output and base 10 only: other formats are read by programs,
+    /// and other bases group digits with `--group`.
+    pub fn localize(&self, text: &str) -> String {
+        if self.format == Format::Plain && self.notation.radix == Radix::default() {
+            self.notation.locale.apply(text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Gives CSV and TSV rows a `rounding_error` column, left empty for
+    /// records without one. Call it before printing anything, so that every
+    /// row matches the header.
+    pub fn add_rounding_error_column(&mut self) {
+        self.rounding_error_column = true;
+    }
+
+    /// Prints `record`, with its result formatted by `format_value` and its
+    /// operands by `format_operand`. Operands are numbers unless the record
+    /// has no operator symbol, as for `eval`.
+    pub fn print(&mut self, record: &Record) -> Result<(), CalcError> {
+        self.remember(record);
+        let mut record = record.clone();
+        record.result = self.format_value(record.ty, &record.result)?;
+        if !record.symbol.is_empty() {
+            if let Some(operands) = record.operands.as_mut() {
+                for operand in operands.iter_mut() {
+                    *operand = self.format_operand(record.ty, operand);
+                }
+            }
+        }
+        self.emit(&record);
+        Ok(())
+    }
+
+    /// Prints a record whose numbers are already in the output radix.
+    pub fn print_formatted(&mut self, record: &Record) {
+        self.remember(record);
+        self.emit(record);
+    }
+
+    /// Prints a record like `print_formatted`, with its result written with
+    /// the separators of `--locale`; the history keeps it as it was.
+    pub fn print_localized(&mut self, record: &Record) {
+        self.remember(record);
+        let mut shown = record.clone();
+        shown.result = self.localize(&record.result);
+        self.emit(&shown);
+    }
+
+    /// Queues `record` for the history, if one is kept. Commands that show
+    /// a result their own way, such as the REPL, call this rather than
+    /// `print`.
+    pub fn remember(&mut self, record: &Record) {
+        if let Some((_, args)) = &self.history {
+            let text = Rendered(Format::Plain, record).to_string();
+            self.unsaved.push(Entry::new(record, text, args));
+        }
+    }
+
+    /// Saves the records printed since the last call to the history, in
+    /// one write. History is best-effort: after a failure a warning is
+    /// printed and no more records are kept.
+    pub fn save_history(&mut self) {
+        let Some((history, _)) = &self.history else {
+            return;
+        };
+        if self.unsaved.is_empty() {
+            return;
+        }
+        if let Err(err) = history.append(&self.unsaved) {
+            eprintln!("warning: history is not saved: {}", err);
+            self.history = None;
+        }
+        self.unsaved.clear();
+    }
+
+    fn emit(&mut self, record: &Record) {
+        let mut record = record.clone();
+        if let Format::Csv | Format::Tsv = self.format {
+            if !self.header_written {
+                self.header_written = true;
+                let mut columns = COLUMNS.to_vec();
+                if self.rounding_error_column {
+                    columns.push("rounding_error");
+                }
+                self.print_header(&columns);
+            }
+            // Rows have the columns of the header, whatever the record holds.
+            record.rounding_error = if self.rounding_error_column {
+                Some(record.rounding_error.unwrap_or_default())
+            } else {
+                None
+            };
+        }
+        println!("{}", Rendered(self.format, &record));
+    }
+
+    /// Prints named values that are not a calculation, such as the parts of
+    /// a float: one `name: value` line each in plain output, only the values
+    /// in bare output, and one object or row in the other formats.
+    pub fn print_fields(&mut self, fields: &[(&str, String)]) {
+        let names: Vec<&str> = fields.iter().map(|(name, _)| *name).collect();
+        let values: Vec<&str> = fields.iter().map(|(_, value)| value.as_str()).collect();
+        match self.format {
+            Format::Plain => {
+                let width = names.iter().map(|name| name.len()).max().unwrap_or(0);
+                for (name, value) in fields {
+                    println!(
+                        "{:<width$}  {}",
+                        format!("{}:", name),
+                        value,
+                        width = width + 1
+                    );
+                }
+            }
+            Format::Bare => {
+                for value in values {
+                    println!("{}", value);
+                }
+            }
+            Format::Json => {
+                let members: Vec<String> = fields
+                    .iter()
+                    .map(|(name, value)| format!("{}:{}", json_string(name), json_string(value)))
+                    .collect();
+                println!("{{{}}}", members.join(","));
+            }
+            Format::Csv | Format::Tsv => {
+                if !self.header_written {
+                    self.header_written = true;
+                    self.print_header(&names);
+                }
+                println!("{}", row(self.format, &values));
+            }
+        }
+    }
+
+    fn print_header(&self, columns: &[&str]) {
+        if let Format::Csv | Format::Tsv = self.format {
+            println!("{}", row(self.format, columns));
+        }
+    }
+}
+
+/// A record displayed in one format, without any header.
+struct Rendered<'a>(Format, &'a Record);
+
+impl fmt::Display for Rendered<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Rendered(format, record) = *self;
+        let operands = record.operands.as_deref();
+        match format {
+            Format::Plain => match operands {
+                // A unary operation, such as `~12` or `popcount 12`.
+                Some([operand]) if !record.symbol.is_empty() => {
+                    let space = if record.symbol.chars().all(char::is_alphabetic) {
+                        " "
+                    } else {
+                        ""
+                    };
+                    write!(
+                        f,
+                        "{}{}{} = {}",
+                        record.symbol, space, operand, record.result
+                    )
+                }
+                Some(operands) => write!(
+                    f,
+                    "{} = {}",
+                    operands.join(&format!(" {} ", record.symbol)),
+                    record.result
+                ),
+                None => f.write_str(&record.result),
+            }
+            .and_then(|_| match &record.rounding_error {
+                Some(error) => write!(f, " (rounding error {})", error),
+                None => Ok(()),
+            }),
+            Format::Bare => f.write_str(&record.result),
+            Format::Json => {
+                write!(f, "{{\"operation\":{},", json_string(record.operation))?;
+                match operands {
+                    Some(operands) => {
+                        let items: Vec<String> = operands.iter().map(|o| json_string(o)).collect();
+                        write!(f, "\"operands\":[{}],", items.join(","))?;
+                    }
+                    None => f.write_str("\"operands\":null,")?,
+                }
+                write!(
+                    f,
+                    "\"result\":{},\"type\":{},\"overflow\":{}",
+                    json_string(&record.result),
+                    json_string(record.ty),
+                    record.overflowed
+                )?;
+                if let Some(error) = &record.rounding_error {
+                    write!(f, ",\"rounding_error\":{}", json_string(error))?;
+                }
+                f.write_str("}")
+            }
+            Format::Csv | Format::Tsv => {
+                let operands = operands
+                    .map(|operands| operands.join(" "))
+                    .unwrap_or_default();
+                let overflowed = record.overflowed.to_string();
+                let mut fields = vec![
+                    record.operation,
+                    &operands,
+                    &record.result,
+                    record.ty,
+                    &overflowed,
+                ];
+                fields.extend(record.rounding_error.as_deref());
+                f.write_str(&row(format, &fields))
+            }
+        }
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Joins fields into one CSV or TSV row.
+fn row(format: Format, fields: &[&str]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if format == Format::Csv {
+                csv_field(field)
+            } else {
+                tsv_field(field)
+            }
+        })
+        .collect();
+    let separator = if format == Format::Csv { "," } else { "\t" };
+    fields.join(separator)
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// TSV has no quoting, so separators inside a field become spaces.
+fn tsv_field(text: &str) -> String {
+    text.replace(['\t', '\n', '\r'], " ")
+}
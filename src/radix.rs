@@ -0,0 +1,220 @@
+//! Integer literals in other bases, and printing results in them.
+//!
+//! Operands may be written as `0x1f`, `0o17` or `0b1010`, with an optional
+//! sign, and any literal may use `_` between digits: `1_000_000`,
+//! `0xdead_beef`. Such literals are rewritten to plain decimal text before
+//! the numeric type parses them.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::bignum::BigInt;
+use crate::decimal::Decimal;
+use crate::error::CalcError;
+use crate::history;
+use crate::number::Number;
+use crate::ops;
+use crate::registers::Registers;
+
+/// Rewrites a literal with a radix prefix or `_` separators to plain
+/// decimal text; other text is returned unchanged.
+pub fn normalize(text: &str) -> Result<String, String> {
+    let (sign, unsigned) = match text.as_bytes().first() {
+        Some(b'-') => ("-", &text[1..]),
+        Some(b'+') => ("", &text[1..]),
+        _ => ("", text),
+    };
+    let prefix = unsigned.get(..2).map(str::to_ascii_lowercase);
+    let radix = match prefix.as_deref() {
+        Some("0x") => 16,
+        Some("0o") => 8,
+        Some("0b") => 2,
+        _ => {
+            return if text.contains('_') {
+                strip_separators(text, 10)
+            } else {
+                Ok(text.to_string())
+            }
+        }
+    };
+    let digits = unsigned[2..].strip_prefix('_').unwrap_or(&unsigned[2..]);
+    let digits = strip_separators(digits, radix)?;
+    let value = BigInt::from_str_radix(&digits, radix)?;
+    Ok(format!("{}{}", sign, value))
+}
+
+/// Parses `text` as a `T`, accepting radix prefixes and separators.
+pub fn parse<T: Number>(text: &str) -> Result<T, String> {
+    T::parse(&normalize(text)?)
+}
+
+/// Removes `_` separators, which must each sit between two digits.
+fn strip_separators(text: &str, radix: u32) -> Result<String, String> {
+    let chars: Vec<char> = text.chars().collect();
+    for (i, c) in chars.iter().enumerate() {
+        if *c != '_' {
+            continue;
+        }
+        let is_digit = |index: Option<usize>| {
+            index
+                .and_then(|index| chars.get(index))
+                .is_some_and(|c| c.is_digit(radix))
+        };
+        if !is_digit(i.checked_sub(1)) || !is_digit(Some(i + 1)) {
+            return Err("`_` must separate two digits".to_string());
+        }
+    }
+    Ok(chars.into_iter().filter(|c| *c != '_').collect())
+}
+
+/// A number given on the command line, in decimal or with a radix prefix,
+/// or a reference to one: `!N` for the result of history entry N, `@NAME`
+/// for the value of register NAME.
+///
+/// Parsing only checks and normalizes the literal; references are looked up
+/// by `resolve`, and the literal becomes a value of the selected numeric
+/// type later, once that type is known.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Operand(String);
+
+impl Operand {
+    /// The operand as normalized text, or the reference as given.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The operand with a reference replaced by the number it refers to,
+    /// reading the history or the registers.
+    pub fn resolve(&self) -> Result<Operand, CalcError> {
+        let value = if let Some(id) = self.0.strip_prefix('!') {
+            let id = id
+                .parse()
+                .expect("history references are checked when parsed");
+            history::recall(id)?
+        } else if let Some(name) = self.0.strip_prefix('@') {
+            Registers::open()?.get(name)?
+        } else {
+            return Ok(self.clone());
+        };
+        normalize(&value)
+            .map(Operand)
+            .map_err(|reason| CalcError::InvalidNumber {
+                text: value,
+                ty: "operand",
+                reason,
+            })
+    }
+
+    /// The value of the operand as a `T`, resolving a reference first.
+    pub fn value<T: Number>(&self) -> Result<T, CalcError> {
+        ops::parse(self.resolve()?.as_str())
+    }
+}
+
+impl FromStr for Operand {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(id) = s.strip_prefix('!') {
+            return match id.parse::<usize>() {
+                Ok(_) => Ok(Operand(s.to_string())),
+                Err(_) => Err(format!("`{}` is not a history entry", s)),
+            };
+        }
+        if s.starts_with('@') {
+            return Ok(Operand(s.to_string()));
+        }
+        normalize(s).map(Operand)
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.0)
+    }
+}
+
+/// How integer results are printed: `--output-base`, `--pad` and `--group`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Radix {
+    base: u32,
+    /// Minimum number of digits, reached with leading zeros.
+    pad: usize,
+    /// Digits between `_` separators, counted from the right.
+    group: Option<usize>,
+}
+
+impl Default for Radix {
+    fn default() -> Self {
+        Radix {
+            base: 10,
+            pad: 0,
+            group: None,
+        }
+    }
+}
+
+impl Radix {
+    pub fn new(base: u32, pad: Option<usize>, group: Option<usize>) -> Result<Self, CalcError> {
+        if !(2..=36).contains(&base) {
+            return Err(CalcError::Usage(format!(
+                "--output-base must be between 2 and 36, got {}",
+                base
+            )));
+        }
+        if group == Some(0) {
+            return Err(CalcError::Usage("--group must be at least 1".to_string()));
+        }
+        Ok(Radix {
+            base,
+            pad: pad.unwrap_or(0),
+            group,
+        })
+    }
+
+    pub fn base(&self) -> u32 {
+        self.base
+    }
+
+    /// Rewrites a decimal result in this radix. Results are printed as they
+    /// are unless a base, padding or grouping was asked for, in which case
+    /// they must be integers.
+    pub fn format(&self, text: &str) -> Result<String, CalcError> {
+        if *self == Radix::default() {
+            return Ok(text.to_string());
+        }
+        let value = text
+            .parse::<Decimal>()
+            .ok()
+            .and_then(|value| value.to_integer())
+            .ok_or_else(|| {
+                CalcError::Usage(format!(
+                    "only integer results can be printed in another base, got `{}`",
+                    text
+                ))
+            })?;
+        // Format widths are limited to u16, so the zeros are built by hand.
+        let digits = value.to_str_radix(self.base);
+        let digits = "0".repeat(self.pad.saturating_sub(digits.len())) + &digits;
+        let digits = match self.group {
+            Some(size) => group(&digits, size),
+            None => digits,
+        };
+        let sign = if value.is_negative() { "-" } else { "" };
+        Ok(format!("{}{}", sign, digits))
+    }
+}
+
+fn group(digits: &str, size: usize) -> String {
+    let chars: Vec<char> = digits.chars().collect();
+    let first = match chars.len() % size {
+        0 => size,
+        first => first,
+    };
+    let mut out: String = chars[..first.min(chars.len())].iter().collect();
+    for chunk in chars[first.min(chars.len())..].chunks(size) {
+        out.push('_');
+        out.extend(chunk);
+    }
+    out
+}
@@ -0,0 +1,253 @@
+//! Exact fractions of arbitrary-precision integers.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::bignum::BigInt;
+use crate::decimal::{Decimal, RoundingMode};
+
+/// A fraction `numer / denom` in lowest terms, with a positive denominator.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Rational {
+    numer: BigInt,
+    denom: BigInt,
+}
+
+impl Rational {
+    /// Builds `numer / denom` in lowest terms, or `None` if `denom` is zero.
+    pub fn new(numer: BigInt, denom: BigInt) -> Option<Rational> {
+        if denom.is_zero() {
+            return None;
+        }
+        let divisor = gcd(&numer, &denom);
+        let (mut numer, _) = numer.div_rem(&divisor)?;
+        let (mut denom, _) = denom.div_rem(&divisor)?;
+        if denom.is_negative() {
+            numer = -&numer;
+            denom = -&denom;
+        }
+        Some(Rational { numer, denom })
+    }
+
+    pub fn from_integer(value: BigInt) -> Rational {
+        Rational {
+            numer: value,
+            denom: BigInt::from(1i64),
+        }
+    }
+
+    pub fn zero() -> Rational {
+        Rational::from_integer(BigInt::zero())
+    }
+
+    pub fn numer(&self) -> &BigInt {
+        &self.numer
+    }
+
+    pub fn denom(&self) -> &BigInt {
+        &self.denom
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numer.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.numer.is_negative()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom == BigInt::from(1i64)
+    }
+
+    pub fn abs(&self) -> Rational {
+        Rational {
+            numer: self.numer.abs(),
+            denom: self.denom.clone(),
+        }
+    }
+
+    pub fn add(&self, rhs: &Rational) -> Rational {
+        Rational::new(
+            &(&self.numer * &rhs.denom) + &(&rhs.numer * &self.denom),
+            &self.denom * &rhs.denom,
+        )
+        .expect("product of denominators is not zero")
+    }
+
+    pub fn sub(&self, rhs: &Rational) -> Rational {
+        self.add(&rhs.neg())
+    }
+
+    pub fn neg(&self) -> Rational {
+        Rational {
+            numer: -&self.numer,
+            denom: self.denom.clone(),
+        }
+    }
+
+    pub fn mul(&self, rhs: &Rational) -> Rational {
+        Rational::new(&self.numer * &rhs.numer, &self.denom * &rhs.denom)
+            .expect("product of denominators is not zero")
+    }
+
+    /// The quotient, or `None` when `rhs` is zero.
+    pub fn div(&self, rhs: &Rational) -> Option<Rational> {
+        Rational::new(&self.numer * &rhs.denom, &self.denom * &rhs.numer)
+    }
+
+    /// Rounds toward zero to an integer.
+    pub fn trunc(&self) -> BigInt {
+        let (quotient, _) = self
+            .numer
+            .div_rem(&self.denom)
+            .expect("denominator is not zero");
+        quotient
+    }
+
+    /// The remainder of a division truncated toward zero, or `None` when
+    /// `rhs` is zero.
+    pub fn rem(&self, rhs: &Rational) -> Option<Rational> {
+        let quotient = Rational::from_integer(self.div(rhs)?.trunc());
+        Some(self.sub(&rhs.mul(&quotient)))
+    }
+
+    /// Raises to an integer power, or `None` for zero to a negative power.
+    pub fn pow(&self, exp: i64) -> Option<Rational> {
+        let magnitude = exp.unsigned_abs() as u32;
+        let numer = self.numer.pow(magnitude);
+        let denom = self.denom.pow(magnitude);
+        if exp < 0 {
+            Rational::new(denom, numer)
+        } else {
+            Rational::new(numer, denom)
+        }
+    }
+
+    /// The value with `scale` fractional digits, rounded with `mode`.
+    pub fn to_decimal(&self, scale: u32, mode: RoundingMode) -> Decimal {
+        Decimal::new(self.numer.clone(), 0)
+            .div(&Decimal::new(self.denom.clone(), 0), Some(scale), mode)
+            .expect("denominator is not zero")
+    }
+
+    /// The exact value as a decimal, if its expansion terminates; that is,
+    /// if the denominator has no prime factors other than 2 and 5.
+    pub fn to_exact_decimal(&self) -> Option<Decimal> {
+        let mut rest = self.denom.clone();
+        let mut twos = 0u32;
+        let mut fives = 0u32;
+        for (factor, count) in [(2i64, &mut twos), (5i64, &mut fives)] {
+            let factor = BigInt::from(factor);
+            loop {
+                let (quotient, remainder) = rest.div_rem(&factor)?;
+                if !remainder.is_zero() {
+                    break;
+                }
+                rest = quotient;
+                *count += 1;
+            }
+        }
+        if rest != BigInt::from(1i64) {
+            return None;
+        }
+        Some(self.to_decimal(twos.max(fives), RoundingMode::Truncate))
+    }
+
+    /// Formats as a decimal if that is exact, and as a fraction otherwise.
+    pub fn to_exact_string(&self) -> String {
+        match self.to_exact_decimal() {
+            Some(decimal) => decimal.to_string(),
+            None => self.to_string(),
+        }
+    }
+
+    /// Formats as a mixed number such as `-1 3/4`.
+    pub fn to_mixed(&self) -> String {
+        let whole = self.trunc();
+        if whole.is_zero() || self.is_integer() {
+            return self.to_string();
+        }
+        let fraction = self.sub(&Rational::from_integer(whole.clone())).abs();
+        format!("{} {}", whole, fraction)
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let lhs = &self.numer * &other.denom;
+        let rhs = &other.numer * &self.denom;
+        Some(lhs.cmp(&rhs))
+    }
+}
+
+fn gcd(a: &BigInt, b: &BigInt) -> BigInt {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while !b.is_zero() {
+        let (_, remainder) = a.div_rem(&b).expect("b is not zero");
+        a = b;
+        b = remainder;
+    }
+    a
+}
+
+impl From<Decimal> for Rational {
+    fn from(value: Decimal) -> Self {
+        let (unscaled, scale) = value.parts();
+        Rational::new(unscaled.clone(), BigInt::pow10(scale)).expect("power of ten is not zero")
+    }
+}
+
+impl FromStr for Rational {
+    type Err = String;
+
+    /// Parses `3/4`, an integer, or a decimal such as `0.75`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('/') {
+            Some((numer, denom)) => {
+                let numer: BigInt = numer.parse()?;
+                let denom: BigInt = denom.parse()?;
+                Rational::new(numer, denom).ok_or_else(|| "denominator is zero".to_string())
+            }
+            None => s.parse::<Decimal>().map(Rational::from),
+        }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            f.pad(&self.numer.to_string())
+        } else {
+            f.pad(&format!("{}/{}", self.numer, self.denom))
+        }
+    }
+}
+
+/// How rational results are printed: `--as-decimal` and `--mixed`.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum FractionStyle {
+    /// Reduced fractions such as `7/4`.
+    #[default]
+    Fraction,
+    /// Mixed numbers such as `1 3/4`.
+    Mixed,
+    /// Decimals with this many fractional digits, rounded with the mode.
+    Decimal(u32, RoundingMode),
+}
+
+impl FractionStyle {
+    /// Rewrites a rational result printed as a fraction in this style.
+    pub fn format(&self, text: &str) -> String {
+        let value: Rational = match text.parse() {
+            Ok(value) => value,
+            Err(_) => return text.to_string(),
+        };
+        match *self {
+            FractionStyle::Fraction => value.to_string(),
+            FractionStyle::Mixed => value.to_mixed(),
+            FractionStyle::Decimal(scale, mode) => value.to_decimal(scale, mode).to_string(),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Named registers that keep values across invocations, like the memory
+//! keys of a pocket calculator.
+//!
+//! Registers live in `$XDG_DATA_HOME/argh-demo/registers`, one
+//! `name<TAB>value` line each. Readers and writers take a lock on
+//! `registers.lock` beside it, and a new version is written to a temporary
+//! file and renamed over the old one, so concurrent shells and interrupted
+//! writes never leave a half-written file.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::path::PathBuf;
+
+use crate::dirs;
+use crate::error::CalcError;
+use crate::expr;
+
+/// The registers of this user.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Registers {
+    dir: PathBuf,
+}
+
+impl Registers {
+    /// The registers in the user's data directory.
+    pub fn open() -> Result<Registers, CalcError> {
+        let dir = dirs::data_dir().ok_or_else(|| {
+            CalcError::Usage("registers need $HOME or $XDG_DATA_HOME to be set".to_string())
+        })?;
+        Ok(Registers { dir })
+    }
+
+    /// Every register, by name.
+    pub fn all(&self) -> Result<BTreeMap<String, String>, CalcError> {
+        let lock = self.lock(false)?;
+        let registers = self.read();
+        drop(lock);
+        registers
+    }
+
+    /// The value of register `name`.
+    pub fn get(&self, name: &str) -> Result<String, CalcError> {
+        self.all()?
+            .remove(name)
+            .ok_or_else(|| CalcError::Usage(format!("there is no register `{}`", name)))
+    }
+
+    /// Sets register `name` to what `update` makes of its current value,
+    /// holding the lock throughout so that no other write comes between
+    /// the read and the write. Returns the new value.
+    pub fn update(
+        &self,
+        name: &str,
+        update: impl FnOnce(Option<&str>) -> Result<String, CalcError>,
+    ) -> Result<String, CalcError> {
+        check_name(name)?;
+        let lock = self.lock(true)?;
+        let mut registers = self.read()?;
+        let value = update(registers.get(name).map(String::as_str))?;
+        registers.insert(name.to_string(), value.clone());
+        self.write(&registers)?;
+        drop(lock);
+        Ok(value)
+    }
+
+    fn path(&self) -> PathBuf {
+        self.dir.join("registers")
+    }
+
+    /// Locks the registers, shared for reading or exclusively for writing,
+    /// until the returned file is dropped.
+    fn lock(&self, exclusive: bool) -> Result<File, CalcError> {
+        let path = self.dir.join("registers.lock");
+        let locked = fs::create_dir_all(&self.dir)
+            .and_then(|()| {
+                OpenOptions::new()
+                    .create(true)
+                    .truncate(false)
+                    .write(true)
+                    .open(&path)
+            })
+            .and_then(|file| {
+                if exclusive {
+                    file.lock()?;
+                } else {
+                    file.lock_shared()?;
+                }
+                Ok(file)
+            });
+        locked.map_err(|err| io_error(path, "lock", err))
+    }
+
+    fn read(&self) -> Result<BTreeMap<String, String>, CalcError> {
+        let path = self.path();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(io_error(path, "read", err)),
+        };
+        Ok(contents
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect())
+    }
+
+    fn write(&self, registers: &BTreeMap<String, String>) -> Result<(), CalcError> {
+        let contents: String = registers
+            .iter()
+            .map(|(name, value)| format!("{}\t{}\n", name, value))
+            .collect();
+        let temporary = self.dir.join("registers.tmp");
+        fs::write(&temporary, contents)
+            .and_then(|()| fs::rename(&temporary, self.path()))
+            .map_err(|err| io_error(self.path(), "write", err))
+    }
+}
+
+/// Register names follow the rules of variable names in expressions.
+fn check_name(name: &str) -> Result<(), CalcError> {
+    let mut chars = name.chars();
+    if chars.next().is_some_and(expr::is_identifier_start) && chars.all(expr::is_identifier_char) {
+        Ok(())
+    } else {
+        Err(CalcError::Usage(format!(
+            "`{}` is not a register name; use letters, digits and `_`, not starting with a digit",
+            name
+        )))
+    }
+}
+
+fn io_error(path: PathBuf, action: &'static str, err: io::Error) -> CalcError {
+    CalcError::Io {
+        path: path.display().to_string(),
+        action,
+        message: err.to_string(),
+    }
+}
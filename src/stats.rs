@@ -0,0 +1,298 @@
+//! Single-pass statistics over streams of `f64` values.
+
+use std::collections::HashMap;
+
+/// Values kept for exact quantiles before switching to a P² estimate.
+pub const EXACT_LIMIT: usize = 1_000_000;
+
+/// Count, extremes, mean and variance by Welford's online algorithm, which
+/// avoids the cancellation of the naive sum-of-squares formula.
+#[derive(Clone, Debug, Default)]
+pub struct Moments {
+    count: u64,
+    mean: f64,
+    /// Sum of squared deviations from the running mean.
+    m2: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+impl Moments {
+    pub fn push(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.mean)
+        }
+    }
+
+    /// The sample variance, or with `population` the population variance.
+    /// `None` without enough values.
+    pub fn variance(&self, population: bool) -> Option<f64> {
+        let divisor = if population {
+            self.count
+        } else {
+            self.count.checked_sub(1)?
+        };
+        if divisor == 0 {
+            None
+        } else {
+            Some(self.m2 / divisor as f64)
+        }
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        self.min
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        self.max
+    }
+}
+
+/// The exact quantile `p` (0 to 1) of sorted values, interpolating linearly
+/// between the closest ranks.
+pub fn quantile_of_sorted(sorted: &[f64], p: f64) -> Option<f64> {
+    let last = sorted.len().checked_sub(1)?;
+    let rank = p * last as f64;
+    let below = rank.floor() as usize;
+    let above = rank.ceil() as usize;
+    Some(sorted[below] + (sorted[above] - sorted[below]) * (rank - below as f64))
+}
+
+/// The P² algorithm (Jain and Chlamtac, 1985): estimates a quantile from a
+/// stream with five markers, in constant memory.
+#[derive(Clone, Debug)]
+pub struct P2 {
+    p: f64,
+    count: usize,
+    /// Marker heights.
+    heights: [f64; 5],
+    /// Actual marker positions, 1-based.
+    positions: [f64; 5],
+    /// Desired marker positions.
+    desired: [f64; 5],
+    increments: [f64; 5],
+}
+
+impl P2 {
+    pub fn new(p: f64) -> Self {
+        P2 {
+            p,
+            count: 0,
+            heights: [0.0; 5],
+            positions: [1.0, 2.0, 3.0, 4.0, 5.0],
+            desired: [1.0, 1.0 + 2.0 * p, 1.0 + 4.0 * p, 3.0 + 2.0 * p, 5.0],
+            increments: [0.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0],
+        }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        if self.count < 5 {
+            self.heights[self.count] = value;
+            self.count += 1;
+            if self.count == 5 {
+                self.heights.sort_by(f64::total_cmp);
+            }
+            return;
+        }
+        self.count += 1;
+        let h = &mut self.heights;
+        let cell = if value < h[0] {
+            h[0] = value;
+            0
+        } else if value >= h[4] {
+            h[4] = value;
+            3
+        } else {
+            (0..4).find(|&i| value < h[i + 1]).unwrap_or(3)
+        };
+        for position in &mut self.positions[cell + 1..] {
+            *position += 1.0;
+        }
+        for (desired, increment) in self.desired.iter_mut().zip(&self.increments) {
+            *desired += increment;
+        }
+        for i in 1..4 {
+            let offset = self.desired[i] - self.positions[i];
+            let room_above = self.positions[i + 1] - self.positions[i];
+            let room_below = self.positions[i - 1] - self.positions[i];
+            if (offset >= 1.0 && room_above > 1.0) || (offset <= -1.0 && room_below < -1.0) {
+                let step = offset.signum();
+                let height = self.parabolic(i, step);
+                self.heights[i] = if self.heights[i - 1] < height && height < self.heights[i + 1] {
+                    height
+                } else {
+                    self.linear(i, step)
+                };
+                self.positions[i] += step;
+            }
+        }
+    }
+
+    /// The estimate, exact while fewer than five values have been seen.
+    pub fn estimate(&self) -> Option<f64> {
+        if self.count >= 5 {
+            return Some(self.heights[2]);
+        }
+        let mut seen = self.heights[..self.count].to_vec();
+        seen.sort_by(f64::total_cmp);
+        quantile_of_sorted(&seen, self.p)
+    }
+
+    fn parabolic(&self, i: usize, step: f64) -> f64 {
+        let (q, n) = (&self.heights, &self.positions);
+        q[i] + step / (n[i + 1] - n[i - 1])
+            * ((n[i] - n[i - 1] + step) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                + (n[i + 1] - n[i] - step) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]))
+    }
+
+    fn linear(&self, i: usize, step: f64) -> f64 {
+        let j = if step > 0.0 { i + 1 } else { i - 1 };
+        self.heights[i]
+            + step * (self.heights[j] - self.heights[i]) / (self.positions[j] - self.positions[i])
+    }
+}
+
+/// A quantile that is exact for up to `EXACT_LIMIT` values and a P²
+/// estimate beyond that.
+#[derive(Clone, Debug)]
+pub struct Quantile {
+    p: f64,
+    values: Option<Vec<f64>>,
+    estimator: P2,
+}
+
+impl Quantile {
+    pub fn new(p: f64) -> Self {
+        Quantile {
+            p,
+            values: Some(Vec::new()),
+            estimator: P2::new(p),
+        }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        self.estimator.push(value);
+        if let Some(values) = &mut self.values {
+            if values.len() < EXACT_LIMIT {
+                values.push(value);
+            } else {
+                self.values = None;
+            }
+        }
+    }
+
+    /// The quantile, and whether it is an estimate.
+    pub fn value(&mut self) -> Option<(f64, bool)> {
+        match &mut self.values {
+            Some(values) => {
+                values.sort_by(f64::total_cmp);
+                quantile_of_sorted(values, self.p).map(|value| (value, false))
+            }
+            None => self.estimator.estimate().map(|value| (value, true)),
+        }
+    }
+}
+
+/// The most frequent values, in ascending order.
+#[derive(Clone, Debug, Default)]
+pub struct Mode {
+    /// Counts keyed by bit pattern, with negative zero folded into zero.
+    counts: HashMap<u64, u64>,
+}
+
+impl Mode {
+    pub fn push(&mut self, value: f64) {
+        let value = if value == 0.0 { 0.0 } else { value };
+        *self.counts.entry(value.to_bits()).or_insert(0) += 1;
+    }
+
+    pub fn modes(&self) -> Vec<f64> {
+        let top = self.counts.values().copied().max().unwrap_or(0);
+        let mut modes: Vec<f64> = self
+            .counts
+            .iter()
+            .filter(|&(_, &count)| count == top)
+            .map(|(&bits, _)| f64::from_bits(bits))
+            .collect();
+        modes.sort_by(f64::total_cmp);
+        modes
+    }
+}
+
+/// Counts of values in equal-width bins over `[min, max]`; the last bin
+/// includes `max`.
+#[derive(Clone, Debug)]
+pub struct Histogram {
+    min: f64,
+    max: f64,
+    counts: Vec<u64>,
+    /// Values outside the range.
+    outside: u64,
+}
+
+impl Histogram {
+    pub fn new(min: f64, max: f64, bins: usize) -> Self {
+        Histogram {
+            min,
+            max,
+            counts: vec![0; bins],
+            outside: 0,
+        }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        if !(self.min..=self.max).contains(&value) {
+            self.outside += 1;
+            return;
+        }
+        let bins = self.counts.len();
+        let width = self.max - self.min;
+        let bin = if width == 0.0 {
+            0
+        } else {
+            (((value - self.min) / width * bins as f64) as usize).min(bins - 1)
+        };
+        self.counts[bin] += 1;
+    }
+
+    /// Each bin's lower bound, upper bound and count.
+    pub fn bins(&self) -> Vec<(f64, f64, u64)> {
+        let width = (self.max - self.min) / self.counts.len() as f64;
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(i, &count)| {
+                let lower = self.min + width * i as f64;
+                let upper = if i + 1 == self.counts.len() {
+                    self.max
+                } else {
+                    self.min + width * (i + 1) as f64
+                };
+                (lower, upper, count)
+            })
+            .collect()
+    }
+
+    pub fn range(&self) -> (f64, f64) {
+        (self.min, self.max)
+    }
+
+    pub fn outside(&self) -> u64 {
+        self.outside
+    }
+}
@@ -0,0 +1,427 @@
+//! Quantities with units, such as `5km`, `2.5GiB`, `3h15m` or `-40°C`.
+//!
+//! Values are exact rationals. Each unit is defined by how it converts to
+//! the base unit of its dimension, so any two units of one dimension
+//! convert into each other, and units of different dimensions never do.
+//!
+//! `m` is both metres and, as in `3h15m`, minutes. It means minutes when
+//! another unit nearby is unambiguously a time, as in `add 3h15m 47m` or
+//! `add 90m --to h`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::decimal::Decimal;
+use crate::error::CalcError;
+use crate::number::Rounding;
+use crate::ops::Operation;
+use crate::rational::Rational;
+
+/// What a unit measures.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Dimension {
+    Length,
+    Mass,
+    Time,
+    DataSize,
+    Temperature,
+}
+
+impl Dimension {
+    pub fn name(self) -> &'static str {
+        match self {
+            Dimension::Length => "length",
+            Dimension::Mass => "mass",
+            Dimension::Time => "time",
+            Dimension::DataSize => "data size",
+            Dimension::Temperature => "temperature",
+        }
+    }
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A unit: a value `v` in it is `(v + offset) * factor` in the base unit of
+/// its dimension. Only temperatures have an offset.
+#[derive(PartialEq, Debug)]
+pub struct Unit {
+    /// The symbol results are printed with, then other accepted spellings.
+    pub names: &'static [&'static str],
+    pub dimension: Dimension,
+    factor: &'static str,
+    offset: &'static str,
+}
+
+macro_rules! units {
+    ($($dimension:ident: $([$($name:literal),+] $factor:literal $(+ $offset:literal)?),+;)+) => {
+        &[$($(Unit {
+            names: &[$($name),+],
+            dimension: Dimension::$dimension,
+            factor: $factor,
+            offset: units!(@offset $($offset)?),
+        }),+),+]
+    };
+    (@offset) => { "0" };
+    (@offset $offset:literal) => { $offset };
+}
+
+/// Every unit, with its size in the base unit of its dimension: metres,
+/// grams, seconds, bytes and kelvins.
+pub const UNITS: &[Unit] = units! {
+    Length:
+        ["m"] "1", ["km"] "1000", ["cm"] "1/100", ["mm"] "1/1000",
+        ["µm", "um"] "1/1000000", ["nm"] "1/1000000000",
+        ["in"] "0.0254", ["ft"] "0.3048", ["yd"] "0.9144", ["mi"] "1609.344",
+        ["nmi"] "1852";
+    Mass:
+        ["g"] "1", ["kg"] "1000", ["mg"] "1/1000", ["t"] "1000000",
+        ["lb"] "453.59237", ["oz"] "28.349523125";
+    Time:
+        ["s", "sec"] "1", ["ms"] "1/1000", ["µs", "us"] "1/1000000",
+        ["ns"] "1/1000000000", ["min", "m"] "60", ["h", "hr"] "3600",
+        ["d", "day", "days"] "86400", ["wk", "week", "weeks"] "604800";
+    DataSize:
+        ["B"] "1", ["bit", "b"] "1/8",
+        ["kB", "KB"] "1000", ["MB"] "1000000", ["GB"] "1000000000",
+        ["TB"] "1000000000000", ["PB"] "1000000000000000",
+        ["KiB"] "1024", ["MiB"] "1048576", ["GiB"] "1073741824",
+        ["TiB"] "1099511627776", ["PiB"] "1125899906842624";
+    Temperature:
+        ["K"] "1", ["°C", "C", "degC"] "1" + "273.15",
+        ["°F", "F", "degF"] "5/9" + "459.67";
+};
+
+impl Unit {
+    /// Looks up a unit by any of its names, which are case-sensitive. A
+    /// name shared by several dimensions, such as `m`, finds the first
+    /// unit listed, metres.
+    pub fn find(name: &str) -> Option<&'static Unit> {
+        UNITS.iter().find(|unit| unit.names.contains(&name))
+    }
+
+    /// Looks up a unit for a quantity that should measure `hint`. A shared
+    /// name is read as the unit of `hint` that has it, so `m` among times is
+    /// minutes, and otherwise as the first unit listed.
+    pub fn find_in(name: &str, hint: Option<Dimension>) -> Result<&'static Unit, String> {
+        hint.and_then(|hint| Unit::named_in(name, hint))
+            .or_else(|| Unit::find(name))
+            .ok_or_else(|| format!("unknown unit `{}`", name))
+    }
+
+    /// The unit called `name` that measures `dimension`, if there is one.
+    fn named_in(name: &str, dimension: Dimension) -> Option<&'static Unit> {
+        UNITS
+            .iter()
+            .find(|unit| unit.dimension == dimension && unit.names.contains(&name))
+    }
+
+    /// The dimension measured by the unit `name`, if no other unit has
+    /// that name.
+    pub fn dimension_of(name: &str) -> Option<Dimension> {
+        let mut named = UNITS.iter().filter(|unit| unit.names.contains(&name));
+        match (named.next(), named.next()) {
+            (Some(unit), None) => Some(unit.dimension),
+            _ => None,
+        }
+    }
+
+    /// The base unit of `dimension`.
+    pub fn base(dimension: Dimension) -> &'static Unit {
+        UNITS
+            .iter()
+            .find(|unit| unit.dimension == dimension && unit.factor == "1" && unit.offset == "0")
+            .expect("every dimension has a base unit")
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        self.names[0]
+    }
+
+    fn factor(&self) -> Rational {
+        self.factor.parse().expect("unit factors are valid")
+    }
+
+    fn offset(&self) -> Rational {
+        self.offset.parse().expect("unit offsets are valid")
+    }
+
+    fn to_base(&self, value: &Rational) -> Rational {
+        value.add(&self.offset()).mul(&self.factor())
+    }
+
+    fn of_base(&self, value: &Rational) -> Rational {
+        value
+            .div(&self.factor())
+            .expect("unit factors are not zero")
+            .sub(&self.offset())
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+/// A value in a unit.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Quantity {
+    pub value: Rational,
+    pub unit: &'static Unit,
+}
+
+/// Binary and decimal data size units for `--human`, from largest to
+/// smallest.
+const BINARY_SIZES: [&str; 6] = ["PiB", "TiB", "GiB", "MiB", "KiB", "B"];
+const DECIMAL_SIZES: [&str; 6] = ["PB", "TB", "GB", "MB", "kB", "B"];
+
+/// Duration units for `--human`, with their lengths in seconds.
+const DURATIONS: [(&str, i64); 3] = [("d", 86400), ("h", 3600), ("m", 60)];
+
+/// Splits `text` into a sign and `(number, unit name)` pairs, as in `-5km`
+/// or `3h 15m`, or returns `None` if it does not have that shape or a name
+/// is not a known unit.
+fn segments(text: &str) -> Option<(bool, Vec<(&str, &str)>)> {
+    let text = text.trim();
+    let (negative, mut rest) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        let end = rest
+            .find(|c: char| !(c.is_ascii_digit() || "./_".contains(c)))
+            .unwrap_or(rest.len());
+        let (number, tail) = rest.split_at(end);
+        let tail = tail.trim_start();
+        let end = tail
+            .find(|c: char| c.is_ascii_digit() || c.is_whitespace())
+            .unwrap_or(tail.len());
+        let (name, tail) = tail.split_at(end);
+        if number.is_empty() || Unit::find(name).is_none() {
+            return None;
+        }
+        segments.push((number, name));
+        rest = tail.trim_start();
+    }
+    if segments.is_empty() {
+        None
+    } else {
+        Some((negative, segments))
+    }
+}
+
+impl Quantity {
+    /// Whether `text` is a number followed by a known unit, or several, as
+    /// in `3h15m`. Other text, such as `3+4i` or `0x1f`, is left to the
+    /// numeric types.
+    pub fn is_quantity(text: &str) -> bool {
+        segments(text).is_some()
+    }
+
+    /// The dimension of the first unit in `text` that only one dimension
+    /// has, used to read a shared name such as `m` in other operands.
+    pub fn hint(text: &str) -> Option<Dimension> {
+        let (_, segments) = segments(text)?;
+        segments
+            .iter()
+            .find_map(|(_, name)| Unit::dimension_of(name))
+    }
+
+    /// Parses `text` as a quantity that should measure `hint`, so that `m`
+    /// is minutes when `hint` is a time; see `Unit::find_in`. Several parts, as in `3h15m`, must go from the
+    /// largest unit to the smallest, each used once, and are added up in
+    /// the unit of the last. Their shared names are read in the dimension
+    /// of the others, so `m` is minutes in `3h15m` and metres in `1km5m`.
+    pub fn parse_in(text: &str, hint: Option<Dimension>) -> Result<Quantity, String> {
+        let (negative, segments) = segments(text).ok_or_else(|| {
+            "expected a number followed by a unit, such as 5km or 3h15m".to_string()
+        })?;
+        let compound = if segments.len() > 1 {
+            Quantity::hint(text)
+        } else {
+            None
+        };
+        let mut parts = Vec::with_capacity(segments.len());
+        for &(number, name) in &segments {
+            let value: Rational = number.replace('_', "").parse()?;
+            let unit = match compound.and_then(|dimension| Unit::named_in(name, dimension)) {
+                Some(unit) => unit,
+                None => Unit::find_in(name, hint)?,
+            };
+            parts.push(Quantity { value, unit });
+        }
+        for pair in parts.windows(2) {
+            let (before, after) = (pair[0].unit, pair[1].unit);
+            if before.dimension == after.dimension && before.factor() <= after.factor() {
+                return Err(format!(
+                    "`{}` comes after `{}`; write the parts from the largest unit to the smallest, each once",
+                    after, before
+                ));
+            }
+        }
+        let last = parts.last().expect("segments are not empty").unit;
+        let mut total = Quantity {
+            value: Rational::zero(),
+            unit: last,
+        };
+        for part in &parts {
+            total = total
+                .combine(Operation::Add, part)
+                .map_err(|err| err.to_string())?;
+        }
+        if negative {
+            total.value = total.value.neg();
+        }
+        Ok(total)
+    }
+
+    /// The same quantity in `unit`, or an error if `unit` measures
+    /// something else.
+    pub fn convert(&self, unit: &'static Unit) -> Result<Quantity, CalcError> {
+        self.check_dimension(unit)?;
+        Ok(Quantity {
+            value: unit.of_base(&self.unit.to_base(&self.value)),
+            unit,
+        })
+    }
+
+    /// The same amount of change in `unit`: like `convert`, but ignoring
+    /// where the scales start, so a difference of `9°F` is `5 °C`, not
+    /// `-12.78 °C`.
+    fn convert_difference(&self, unit: &'static Unit) -> Result<Quantity, CalcError> {
+        self.check_dimension(unit)?;
+        Ok(Quantity {
+            value: self
+                .value
+                .mul(&self.unit.factor())
+                .div(&unit.factor())
+                .expect("unit factors are not zero"),
+            unit,
+        })
+    }
+
+    fn check_dimension(&self, unit: &'static Unit) -> Result<(), CalcError> {
+        if self.unit.dimension == unit.dimension {
+            return Ok(());
+        }
+        Err(CalcError::Dimension {
+            value: self.to_string(),
+            from: self.unit.dimension.name(),
+            unit: unit.symbol(),
+            to: unit.dimension.name(),
+        })
+    }
+
+    /// Adds or subtracts `rhs`, converted to the unit of `self`. `rhs` is
+    /// taken as a difference, so `10°C + 9°F` is `15 °C`.
+    pub fn combine(&self, op: Operation, rhs: &Quantity) -> Result<Quantity, CalcError> {
+        let rhs = rhs.convert_difference(self.unit)?;
+        let value = match op {
+            Operation::Add => self.value.add(&rhs.value),
+            Operation::Sub => self.value.sub(&rhs.value),
+            _ => {
+                return Err(CalcError::Usage(format!(
+                    "{} is not supported for quantities with units",
+                    op
+                )))
+            }
+        };
+        Ok(Quantity {
+            value,
+            unit: self.unit,
+        })
+    }
+
+    /// Formats the value exactly when it has a terminating decimal
+    /// expansion, and otherwise with `rounding.scale` digits, or up to the
+    /// default division scale.
+    pub fn display(&self, rounding: &Rounding) -> String {
+        let value = match (rounding.scale, self.value.to_exact_decimal()) {
+            (None, Some(exact)) => exact,
+            (scale, _) => Decimal::new(self.value.numer().clone(), 0)
+                .div(
+                    &Decimal::new(self.value.denom().clone(), 0),
+                    scale,
+                    rounding.mode,
+                )
+                .expect("denominator is not zero"),
+        };
+        format!("{} {}", value, self.unit)
+    }
+
+    /// Formats for reading: data sizes in the largest unit of their family,
+    /// binary or decimal, that keeps the value at least 1, such as
+    /// `1.85 GiB`; durations as days, hours, minutes and seconds, such as
+    /// `4h 2m`. Values are rounded to `rounding.scale` digits, by default 2
+    /// for sizes and 3 for seconds. Other quantities use `display`.
+    pub fn human(&self, rounding: &Rounding) -> String {
+        match self.unit.dimension {
+            Dimension::DataSize => {
+                let sizes = if self.unit.symbol().ends_with("iB") {
+                    BINARY_SIZES
+                } else {
+                    DECIMAL_SIZES
+                };
+                let one = Rational::from_integer(1i64.into());
+                let size = sizes
+                    .iter()
+                    .map(|name| {
+                        self.convert(Unit::find(name).expect("size units are known"))
+                            .expect("sizes are data sizes")
+                    })
+                    .find(|size| {
+                        size.value.abs() >= one || size.unit == Unit::base(Dimension::DataSize)
+                    })
+                    .expect("bytes are always a candidate");
+                let value = size
+                    .value
+                    .to_decimal(rounding.scale.unwrap_or(2), rounding.mode);
+                format!("{} {}", value.normalize(), size.unit)
+            }
+            Dimension::Time => {
+                let seconds = self
+                    .convert(Unit::base(Dimension::Time))
+                    .expect("durations are times")
+                    .value;
+                let mut rest = seconds.abs();
+                let mut parts = Vec::new();
+                for (name, length) in DURATIONS {
+                    let length = Rational::from_integer(length.into());
+                    let count = rest.div(&length).expect("lengths are not zero").trunc();
+                    if !count.is_zero() {
+                        rest = rest.sub(&length.mul(&Rational::from_integer(count.clone())));
+                        parts.push(format!("{}{}", count, name));
+                    }
+                }
+                if !rest.is_zero() || parts.is_empty() {
+                    let rest = rest.to_decimal(rounding.scale.unwrap_or(3), rounding.mode);
+                    parts.push(format!("{}s", rest.normalize()));
+                }
+                let sign = if seconds.is_negative() { "-" } else { "" };
+                format!("{}{}", sign, parts.join(" "))
+            }
+            _ => self.display(rounding),
+        }
+    }
+}
+
+impl FromStr for Quantity {
+    type Err = String;
+
+    /// Parses a number followed by a unit, such as `5km`, `1/2 mi`, `-40°C`
+    /// or `3h15m`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Quantity::parse_in(s, None)
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.value.to_exact_string(), self.unit)
+    }
+}
@@ -0,0 +1,263 @@
+mod common;
+
+use argh_demo::bignum::BigInt;
+use argh_demo::decimal::{Decimal, RoundingMode};
+use argh_demo::ops::{self, Operation, Overflow};
+use argh_demo::Rounding;
+use common::Sandbox;
+
+fn big(text: &str) -> BigInt {
+    text.parse().unwrap()
+}
+
+fn decimal(text: &str) -> Decimal {
+    text.parse().unwrap()
+}
+
+fn div_rem(u: &str, v: &str) -> (String, String) {
+    let (quotient, remainder) = big(u).div_rem(&big(v)).unwrap();
+    (quotient.to_string(), remainder.to_string())
+}
+
+#[test]
+fn divides_by_long_divisors() {
+    // The first estimate of each quotient digit is one too large here, so
+    // the divisor has to be added back.
+    assert_eq!(
+        div_rem(
+            "170141183420855150474555134919112130560",
+            "39614081257132168796771975169"
+        ),
+        (
+            "4294967294".to_string(),
+            "39614081257132168792477007874".to_string()
+        )
+    );
+    assert_eq!(
+        div_rem(
+            "170141183460469231731687303715884105731",
+            "9903520314283042199192993793"
+        ),
+        (
+            "17179869183".to_string(),
+            "9903520314283042182013124612".to_string()
+        )
+    );
+    assert_eq!(
+        div_rem(
+            "340282366920938463444927863358058659839",
+            "79228162514264337589248983041"
+        ),
+        (
+            "4294967295".to_string(),
+            "79228162514264337584954015744".to_string()
+        )
+    );
+    // Quotients truncate toward zero, and remainders take the dividend's sign.
+    assert_eq!(
+        div_rem("-100000000000000000000", "30000000000"),
+        ("-3333333333".to_string(), "-10000000000".to_string())
+    );
+    assert_eq!(div_rem("7", "-2"), ("-3".to_string(), "1".to_string()));
+    assert!(big("1").div_rem(&BigInt::zero()).is_none());
+}
+
+#[test]
+fn round_trips_text() {
+    for text in [
+        "0",
+        "-1",
+        "4294967295",
+        "4294967296",
+        "-18446744073709551616",
+        "1000000000000000000000000000000000000001",
+    ] {
+        assert_eq!(big(text).to_string(), text);
+    }
+    assert_eq!(big("-0").to_string(), "0");
+    let value = big("-123456789012345678901234567890");
+    for radix in [2, 16, 36] {
+        let digits = value.to_str_radix(radix);
+        assert_eq!(BigInt::from_str_radix(&digits, radix).unwrap(), value.abs());
+    }
+    assert_eq!(big("255").to_str_radix(16), "ff");
+    for text in ["0.5", "-12.250", "0.000001", "123456789012345678901.5"] {
+        assert_eq!(decimal(text).to_string(), text);
+    }
+}
+
+#[test]
+fn rounds_in_each_mode() {
+    let round =
+        |text: &str, scale: u32, mode: RoundingMode| decimal(text).round(scale, mode).to_string();
+    let cases = [
+        // value, scale, half-even, half-up, truncate
+        ("2.5", 0, "2", "3", "2"),
+        ("3.5", 0, "4", "4", "3"),
+        ("-2.5", 0, "-2", "-3", "-2"),
+        ("1.2345", 3, "1.234", "1.235", "1.234"),
+        ("1.2355", 3, "1.236", "1.236", "1.235"),
+        ("-1.2351", 3, "-1.235", "-1.235", "-1.235"),
+        ("0.999", 2, "1.00", "1.00", "0.99"),
+        ("1.5", 3, "1.500", "1.500", "1.500"),
+    ];
+    for (text, scale, half_even, half_up, truncate) in cases {
+        assert_eq!(
+            round(text, scale, RoundingMode::HalfEven),
+            half_even,
+            "{}",
+            text
+        );
+        assert_eq!(
+            round(text, scale, RoundingMode::HalfUp),
+            half_up,
+            "{}",
+            text
+        );
+        assert_eq!(
+            round(text, scale, RoundingMode::Truncate),
+            truncate,
+            "{}",
+            text
+        );
+    }
+}
+
+#[test]
+fn divides_decimals_to_the_default_scale() {
+    let third = decimal("1")
+        .div(&decimal("3"), None, RoundingMode::HalfEven)
+        .unwrap();
+    assert_eq!(third.to_string(), "0.33333333333333333333");
+    let half = decimal("1")
+        .div(&decimal("2"), None, RoundingMode::HalfEven)
+        .unwrap();
+    assert_eq!(half.to_string(), "0.5");
+    let two_thirds = decimal("2")
+        .div(&decimal("3"), Some(2), RoundingMode::Truncate)
+        .unwrap();
+    assert_eq!(two_thirds.to_string(), "0.66");
+}
+
+#[test]
+fn bounds_powers() {
+    let pow = |base: &str, exp: &str| {
+        ops::apply(
+            Operation::Pow,
+            &big(base),
+            &big(exp),
+            Overflow::Checked,
+            &Rounding::default(),
+        )
+        .map(|(value, _)| value)
+    };
+    let err = pow("10", "4000000000").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "10 ^ 4000000000 is undefined: result is too large"
+    );
+    assert_eq!(pow("1", "4000000000").unwrap(), big("1"));
+    assert_eq!(pow("-1", "4000000001").unwrap(), big("-1"));
+    assert_eq!(
+        pow("2", "100").unwrap().to_string(),
+        "1267650600228229401496703205376"
+    );
+    assert_eq!(pow("2", "262143").unwrap().bits(), 262_144);
+    assert!(pow("2", "262144").is_err());
+}
+
+#[test]
+fn bounds_decimal_powers_by_scale() {
+    let pow = |base: &str, exp: &str| {
+        ops::apply(
+            Operation::Pow,
+            &decimal(base),
+            &decimal(exp),
+            Overflow::Checked,
+            &Rounding::default(),
+        )
+        .map(|(value, _)| value)
+    };
+    let err = pow("0.001", "100000").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "0.001 ^ 100000 is undefined: result is too large"
+    );
+    let tiny = pow("0.1", "70000").unwrap();
+    assert_eq!(tiny.parts().1, 70_000);
+    assert!(pow("0.1", "100001").is_err());
+}
+
+#[test]
+fn prints_scales_beyond_format_widths() {
+    // Format widths stop at 65535, so long runs of zeros are built by hand.
+    let text = decimal("1e-70000").to_string();
+    assert_eq!(text.len(), 70_002);
+    assert!(text.starts_with("0.000"));
+    assert!(text.ends_with("0001"));
+    assert_eq!(decimal("-1e-70000").to_string()[..4], *"-0.0");
+    assert_eq!(decimal("-12.5").to_string(), "-12.5");
+    assert_eq!(decimal("0.005").to_string(), "0.005");
+}
+
+#[test]
+fn refuses_scales_past_the_limit() {
+    let sandbox = Sandbox::new("bignum");
+    for args in [
+        &[
+            "--decimal",
+            "--scale",
+            "170000",
+            "div",
+            "--num1",
+            "1",
+            "--num2",
+            "3",
+        ][..],
+        &[
+            "--type",
+            "rational",
+            "--as-decimal",
+            "170000",
+            "div",
+            "--num1",
+            "1",
+            "--num2",
+            "3",
+        ],
+    ] {
+        let mut all = vec!["--no-history"];
+        all.extend_from_slice(args);
+        let output = sandbox.run(&all);
+        assert_eq!(output.code, 2, "{:?}", args);
+        assert!(
+            output
+                .stderr
+                .contains("more than the 100000 digits allowed"),
+            "{}",
+            output.stderr
+        );
+    }
+    let output = sandbox.run(&[
+        "--no-history",
+        "--decimal",
+        "pow",
+        "--num1",
+        "0.001",
+        "--num2",
+        "100000",
+    ]);
+    assert_eq!(output.code, 4);
+    let product = sandbox.stdout(&[
+        "--no-history",
+        "--decimal",
+        "--format",
+        "bare",
+        "mul",
+        "--num1",
+        "1e-40000",
+        "--num2",
+        "1e-40000",
+    ]);
+    assert_eq!(product.trim_end().len(), 80_002);
+}
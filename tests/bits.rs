@@ -0,0 +1,84 @@
+mod common;
+
+use common::Sandbox;
+
+fn show_bits(args: &[&str]) -> String {
+    let sandbox = Sandbox::new("bits");
+    let mut all = vec!["--no-history"];
+    all.extend_from_slice(args);
+    all.push("--show-bits");
+    sandbox.stdout(&all)
+}
+
+#[test]
+fn shows_signed_and_unsigned_patterns() {
+    assert_eq!(
+        show_bits(&["--type", "i8", "bit", "and", "--num1", "-1", "--num2", "5"]),
+        "  1111 1111  -1\n& 0000 0101  5\n= 0000 0101  5\n"
+    );
+    assert_eq!(
+        show_bits(&["--type", "u8", "bit", "not", "--num", "5"]),
+        "  0000 0101  5\n~ 1111 1010  250\n"
+    );
+    assert_eq!(
+        show_bits(&["--type", "u16", "bit", "shl", "--num", "1", "--by", "15"]),
+        "      0000 0000 0000 0001  1\n<< 15 1000 0000 0000 0000  32768\n"
+    );
+}
+
+#[test]
+fn names_negative_values_in_another_base() {
+    assert_eq!(
+        show_bits(&[
+            "--type",
+            "i8",
+            "--output-base",
+            "16",
+            "bit",
+            "not",
+            "--num",
+            "5"
+        ]),
+        "  0000 0101  5\n~ 1111 1010  fa (-6)\n"
+    );
+    assert_eq!(
+        show_bits(&[
+            "--type",
+            "u8",
+            "--output-base",
+            "16",
+            "bit",
+            "not",
+            "--num",
+            "5"
+        ]),
+        "  0000 0101  5\n~ 1111 1010  fa\n"
+    );
+}
+
+#[test]
+fn counts_set_bits() {
+    assert_eq!(
+        show_bits(&["--type", "i16", "bit", "popcount", "--num", "-1"]),
+        "         1111 1111 1111 1111  -1\npopcount                      16\n"
+    );
+}
+
+#[test]
+fn refuses_machine_readable_formats() {
+    let sandbox = Sandbox::new("bits");
+    let output = sandbox.run(&[
+        "--no-history",
+        "--format",
+        "json",
+        "bit",
+        "not",
+        "--num",
+        "5",
+        "--show-bits",
+    ]);
+    assert_eq!(output.code, 2);
+    assert!(output
+        .stderr
+        .contains("--show-bits only works with --format plain"));
+}
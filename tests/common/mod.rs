@@ -0,0 +1,99 @@
+//! Runs the built program in a directory of its own, so that tests neither
+//! read nor write the history, registers and configuration of the user.
+
+#![allow(dead_code)]
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static SANDBOXES: AtomicUsize = AtomicUsize::new(0);
+
+/// A home for the program, removed when dropped.
+pub struct Sandbox {
+    dir: PathBuf,
+}
+
+/// What a run of the program printed, and how it exited.
+pub struct Output {
+    pub code: i32,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl Sandbox {
+    /// A new, empty home; `name` says which test it is for.
+    pub fn new(name: &str) -> Sandbox {
+        let dir = env::temp_dir().join(format!(
+            "argh-demo-{}-{}-{}",
+            name,
+            std::process::id(),
+            SANDBOXES.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        Sandbox { dir }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Runs the program with `args` and the variables in `vars`.
+    pub fn run_with(&self, vars: &[(&str, &str)], args: &[&str]) -> Output {
+        self.run_input(vars, args, "")
+    }
+
+    /// Runs the program with `input` on its standard input.
+    pub fn run_input(&self, vars: &[(&str, &str)], args: &[&str], input: &str) -> Output {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_argh-demo"));
+        for (name, _) in env::vars() {
+            if name.starts_with("ARGH_DEMO_") {
+                command.env_remove(name);
+            }
+        }
+        let mut child = command
+            .env("HOME", &self.dir)
+            .env("XDG_DATA_HOME", self.dir.join("data"))
+            .env("XDG_CONFIG_HOME", self.dir.join("config"))
+            .envs(vars.iter().copied())
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(input.as_bytes())
+            .unwrap();
+        let output = child.wait_with_output().unwrap();
+        Output {
+            code: output.status.code().unwrap_or(-1),
+            stdout: String::from_utf8(output.stdout).unwrap(),
+            stderr: String::from_utf8(output.stderr).unwrap(),
+        }
+    }
+
+    pub fn run(&self, args: &[&str]) -> Output {
+        self.run_with(&[], args)
+    }
+
+    /// Runs the program, expecting it to succeed, and returns its output.
+    pub fn stdout(&self, args: &[&str]) -> String {
+        let output = self.run(args);
+        assert_eq!(output.code, 0, "{:?}: {}", args, output.stderr);
+        output.stdout
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
@@ -0,0 +1,127 @@
+use argh_demo::completions::{self, Shell};
+use argh_demo::help::{self, Command};
+
+/// Every subcommand of `DemoCli`; a new one must be added here, which is a
+/// reminder that its completions are checked too.
+const SUBCOMMANDS: [&str; 21] = [
+    "add",
+    "sub",
+    "mul",
+    "div",
+    "rem",
+    "pow",
+    "eval",
+    "repl",
+    "run",
+    "bit",
+    "inspect",
+    "abs",
+    "arg",
+    "conj",
+    "stats",
+    "convert",
+    "date",
+    "completions",
+    "history",
+    "store",
+    "recall",
+];
+
+fn find<'a>(tree: &'a Command, path: &[&str]) -> &'a Command {
+    path.iter().fold(tree, |command, name| {
+        command
+            .subcommands
+            .iter()
+            .find(|subcommand| subcommand.name() == *name)
+            .unwrap_or_else(|| panic!("no `{}` under `{}`", name, command.key()))
+    })
+}
+
+fn longs(command: &Command) -> Vec<&str> {
+    command
+        .options
+        .iter()
+        .map(|option| option.long.as_str())
+        .collect()
+}
+
+#[test]
+fn tree_has_every_subcommand() {
+    let tree = help::command_tree();
+    let names: Vec<&str> = tree.subcommands.iter().map(Command::name).collect();
+    assert_eq!(names, SUBCOMMANDS);
+}
+
+#[test]
+fn tree_reads_options_and_nested_commands() {
+    let tree = help::command_tree();
+    assert!(longs(&tree).contains(&"--type"));
+    let histogram = find(&tree, &["stats", "histogram"]);
+    assert_eq!(
+        longs(histogram),
+        ["--bins", "--min", "--max", "--num", "--file", "--stdin", "--help"]
+    );
+    assert_eq!(
+        histogram.description,
+        "Count values in equal-width bins; not kept in the history"
+    );
+    let bins = &histogram.options[0];
+    assert!(bins.takes_value);
+    assert_eq!(bins.description, "the number of bins (default 10)");
+    let stdin = &histogram.options[5];
+    assert!(!stdin.takes_value);
+    // A description wrapped over several lines of help is joined.
+    let min = &histogram.options[1];
+    assert!(min
+        .description
+        .ends_with("held in memory (default the smallest value)"));
+    assert_eq!(
+        find(&tree, &["date", "add"]).path,
+        ["argh-demo", "date", "add"]
+    );
+}
+
+#[test]
+fn scripts_cover_every_subcommand_and_option() {
+    let tree = help::command_tree();
+    for shell in Shell::ALL {
+        let script = completions::generate(shell);
+        for command in tree.walk() {
+            assert!(
+                script.contains(&command.key()),
+                "{} script is missing `{}`",
+                shell.name(),
+                command.key()
+            );
+            for subcommand in &command.subcommands {
+                assert!(
+                    script.contains(subcommand.name()),
+                    "{} script is missing `{}`",
+                    shell.name(),
+                    subcommand.key()
+                );
+            }
+            for option in &command.options {
+                let expected = match shell {
+                    Shell::Fish => format!("-l {}", option.long.trim_start_matches('-')),
+                    _ => option.long.clone(),
+                };
+                assert!(
+                    script.contains(&expected),
+                    "{} script is missing `{}` of `{}`",
+                    shell.name(),
+                    option.long,
+                    command.key()
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn shells_parse_by_name() {
+    for shell in Shell::ALL {
+        assert_eq!(shell.name().parse::<Shell>(), Ok(shell));
+    }
+    assert!("tcsh".parse::<Shell>().is_err());
+}
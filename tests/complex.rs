@@ -0,0 +1,121 @@
+mod common;
+
+use common::Sandbox;
+
+fn complex(args: &[&str]) -> String {
+    let sandbox = Sandbox::new("complex");
+    let mut all = vec!["--no-history", "--type", "complex"];
+    all.extend_from_slice(args);
+    sandbox.stdout(&all).trim_end().to_string()
+}
+
+fn complex_error(args: &[&str]) -> (i32, String) {
+    let sandbox = Sandbox::new("complex");
+    let mut all = vec!["--no-history", "--type", "complex"];
+    all.extend_from_slice(args);
+    let output = sandbox.run(&all);
+    (output.code, output.stderr)
+}
+
+#[test]
+fn reads_rectangular_and_polar_literals() {
+    assert_eq!(complex(&["add", "3+4i", "0"]), "3+4i + 0 = 3+4i");
+    assert_eq!(complex(&["add", "2i", "3"]), "2i + 3 = 3+2i");
+    assert_eq!(
+        complex(&["--format", "bare", "add", "5∠90°", "0"]),
+        "0.0000000000000003061616997868383+5i"
+    );
+    assert_eq!(
+        complex(&["abs", "--num", "5∠53.13°"]),
+        "abs 3.0000071456633126+3.999994640742543i = 5"
+    );
+}
+
+#[test]
+fn refuses_bad_literals() {
+    for text in &["3+4x", "5∠", "i+"] {
+        let (code, stderr) = complex_error(&["add", text, "1"]);
+        assert_eq!(code, 2, "{}", text);
+        assert!(
+            stderr.starts_with(&format!("error: `{}` is not a valid complex", text)),
+            "{}",
+            stderr
+        );
+    }
+}
+
+#[test]
+fn does_arithmetic() {
+    assert_eq!(complex(&["add", "3+4i", "1-2i"]), "3+4i + 1-2i = 4+2i");
+    assert_eq!(
+        complex(&["sub", "--num1", "3+4i", "--num2", "1-2i"]),
+        "3+4i - 1-2i = 2+6i"
+    );
+    assert_eq!(
+        complex(&["mul", "--num1", "3+4i", "--num2", "1-2i"]),
+        "3+4i * 1-2i = 11-2i"
+    );
+    assert_eq!(
+        complex(&["div", "--num1", "3+4i", "--num2", "1-2i"]),
+        "3+4i / 1-2i = -1+2i"
+    );
+    assert_eq!(
+        complex(&["pow", "--num1", "1+1i", "--num2", "2"]),
+        "1+1i ^ 2 = 2i"
+    );
+}
+
+#[test]
+fn refuses_division_by_zero() {
+    for zero in &["0", "0+0i"] {
+        let (code, stderr) = complex_error(&["div", "--num1", "3+4i", "--num2", zero]);
+        assert_eq!(code, 4);
+        assert_eq!(stderr, "error: 3+4i / 0 divides by zero\n");
+    }
+}
+
+#[test]
+fn gives_magnitude_angle_and_conjugate() {
+    assert_eq!(complex(&["abs", "--num", "3+4i"]), "abs 3+4i = 5");
+    assert_eq!(
+        complex(&["arg", "--num", "1+1i"]),
+        "arg 1+1i = 0.7853981633974483"
+    );
+    assert_eq!(
+        complex(&["arg", "--num", "3+4i", "--degrees"]),
+        "arg 3+4i = 53.13010235415598"
+    );
+    assert_eq!(complex(&["conj", "--num", "3+4i"]), "conj 3+4i = 3-4i");
+    // The magnitude and angle are real, whatever the complex form.
+    assert_eq!(
+        complex(&["--complex-form", "polar", "abs", "--num", "3+4i"]),
+        "abs 5∠53.13010235415598° = 5"
+    );
+    assert_eq!(
+        complex(&["--format", "json", "arg", "--num", "1"]),
+        r#"{"operation":"arg","operands":["1"],"result":"0","type":"f64","overflow":false}"#
+    );
+    let sandbox = Sandbox::new("complex");
+    let output = sandbox.run(&["--no-history", "--type", "f64", "abs", "--num", "3"]);
+    assert_eq!(output.code, 2);
+    assert_eq!(
+        output.stderr,
+        "error: this operation needs --type complex, not f64\n"
+    );
+}
+
+#[test]
+fn prints_in_either_form() {
+    assert_eq!(
+        complex(&["--complex-form", "polar", "add", "3+4i", "0"]),
+        "5∠53.13010235415598° + 0∠0° = 5∠53.13010235415598°"
+    );
+    assert_eq!(
+        complex(&["--complex-form", "rectangular", "add", "3+4i", "0"]),
+        "3+4i + 0 = 3+4i"
+    );
+    assert_eq!(
+        complex(&["--complex-form", "polar", "conj", "--num", "1+1i"]),
+        "conj 1.4142135623730951∠45° = 1.4142135623730951∠-45°"
+    );
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use std::fs;
+
+use argh_demo::config::Defaults;
+use argh_demo::decimal::RoundingMode;
+use argh_demo::number::{NumType, Precision};
+use argh_demo::output::Format;
+use common::Sandbox;
+
+/// Writes `text` as a configuration file in `sandbox`, returning its path.
+fn config(sandbox: &Sandbox, text: &str) -> String {
+    let path = sandbox.path().join("config.toml");
+    fs::write(&path, text).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn reads_toml_and_ini_files() {
+    let sandbox = Sandbox::new("config");
+    let toml = config(
+        &sandbox,
+        concat!(
+            "# defaults\n",
+            "[defaults]\n",
+            "type = \"f64\"\n",
+            "precision = 'fixed'\n",
+            "scale = 4\n",
+            "format = \"json\" # trailing comment\n",
+            "rounding = \"half-up\"\n",
+            "locale = \"de_DE.UTF-8\"\n",
+        ),
+    );
+    assert_eq!(
+        Defaults::load(Some(&toml)).unwrap(),
+        Defaults {
+            num_type: Some(NumType::F64),
+            precision: Some(Precision::Fixed),
+            scale: Some(4),
+            format: Some(Format::Json),
+            rounding: Some(RoundingMode::HalfUp),
+            locale: Some("de".parse().unwrap()),
+        }
+    );
+    let ini = config(&sandbox, "; no header\nformat = csv ; comment\n\n");
+    assert_eq!(
+        Defaults::load(Some(&ini)).unwrap(),
+        Defaults {
+            format: Some(Format::Csv),
+            ..Defaults::default()
+        }
+    );
+}
+
+#[test]
+fn refuses_malformed_files() {
+    let sandbox = Sandbox::new("config");
+    let cases = [
+        ("colour = red\n", "unknown key `colour`"),
+        ("type\n", "expected `key = value`"),
+        ("[output]\nformat = json\n", "unknown section `output`"),
+        ("scale = -1\n", "scale must be a number of digits"),
+        ("type = i7\n", "i7"),
+        ("locale = xx_XX\n", "unknown locale `xx_XX`"),
+    ];
+    for (text, reason) in cases {
+        let path = config(&sandbox, text);
+        let err = Defaults::load(Some(&path)).unwrap_err();
+        assert_eq!(err.exit_code(), 2, "{}", text);
+        assert!(err.to_string().contains(reason), "{}: {}", text, err);
+    }
+    let err = Defaults::load(Some("/nonexistent/argh-demo.toml")).unwrap_err();
+    assert_eq!(err.exit_code(), 5);
+}
+
+#[test]
+fn lets_options_override_variables_override_files() {
+    let sandbox = Sandbox::new("config");
+    let dir = sandbox.path().join("config/argh-demo");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("config.toml"),
+        "type = \"decimal\"\nscale = 2\nformat = \"bare\"\n",
+    )
+    .unwrap();
+    let div = ["--no-history", "div", "--num1", "2", "--num2", "3"];
+    let run = |vars: &[(&str, &str)], options: &[&str]| {
+        let args: Vec<&str> = options.iter().chain(&div).copied().collect();
+        let output = sandbox.run_with(vars, &args);
+        assert_eq!(output.code, 0, "{}", output.stderr);
+        output.stdout
+    };
+    assert_eq!(run(&[], &[]), "0.67\n");
+    assert_eq!(run(&[("ARGH_DEMO_SCALE", "4")], &[]), "0.6667\n");
+    assert_eq!(
+        run(
+            &[("ARGH_DEMO_SCALE", "4"), ("ARGH_DEMO_ROUNDING", "truncate")],
+            &[]
+        ),
+        "0.6666\n"
+    );
+    assert_eq!(run(&[("ARGH_DEMO_SCALE", "4")], &["--scale", "1"]), "0.7\n");
+    assert_eq!(run(&[("ARGH_DEMO_FORMAT", "plain")], &[]), "2 / 3 = 0.67\n");
+    assert_eq!(run(&[], &["--type", "i64"]), "0\n");
+    assert_eq!(
+        run(&[("ARGH_DEMO_TYPE", "i64")], &["--format", "plain"]),
+        "2 / 3 = 0\n"
+    );
+
+    // A file named with --config or $ARGH_DEMO_CONFIG replaces the one in
+    // the config directory.
+    let other = config(&sandbox, "type = decimal\nscale = 3\n");
+    assert_eq!(run(&[], &["--config", &other]), "2 / 3 = 0.667\n");
+    assert_eq!(
+        run(&[("ARGH_DEMO_CONFIG", other.as_str())], &[]),
+        "2 / 3 = 0.667\n"
+    );
+
+    let bad = sandbox.run_with(&[("ARGH_DEMO_SCALE", "many")], &div);
+    assert_eq!(bad.code, 2);
+    assert!(bad.stderr.contains("$ARGH_DEMO_SCALE"), "{}", bad.stderr);
+}
+
+#[test]
+fn writes_plain_numbers_in_the_locale() {
+    let sandbox = Sandbox::new("config");
+    let run = |vars: &[(&str, &str)], args: &[&str]| {
+        let mut all = vec!["--no-history"];
+        all.extend_from_slice(args);
+        let output = sandbox.run_with(vars, &all);
+        assert_eq!(output.code, 0, "{}", output.stderr);
+        output.stdout.trim_end().to_string()
+    };
+    let sum = ["--decimal", "add", "1234567.891", "2"];
+    assert_eq!(run(&[], &sum), "1234567.891 + 2 = 1234569.891");
+    let german = [&["--locale", "de_DE"][..], &sum].concat();
+    assert_eq!(run(&[], &german), "1.234.567,891 + 2 = 1.234.569,891");
+    let english = [&["--locale", "en_US.UTF-8"][..], &sum].concat();
+    assert_eq!(run(&[], &english), "1,234,567.891 + 2 = 1,234,569.891");
+    let swiss = [&["--locale", "de_CH"][..], &sum].concat();
+    assert_eq!(run(&[], &swiss), "1’234’567.891 + 2 = 1’234’569.891");
+    assert_eq!(
+        run(&[("ARGH_DEMO_LOCALE", "fr")], &sum),
+        "1\u{a0}234\u{a0}567,891 + 2 = 1\u{a0}234\u{a0}569,891"
+    );
+    // The option wins over the environment, and C writes numbers as given.
+    let plain = [&["--locale", "C"][..], &sum].concat();
+    assert_eq!(
+        run(&[("ARGH_DEMO_LOCALE", "de")], &plain),
+        "1234567.891 + 2 = 1234569.891"
+    );
+    // Other formats and bases are left alone, so that they read back.
+    let json = [&["--locale", "de", "--format", "bare"][..], &sum].concat();
+    assert_eq!(run(&[], &json), "1234569.891");
+    assert_eq!(
+        run(
+            &[],
+            &["--locale", "de", "--output-base", "16", "add", "4096", "1"]
+        ),
+        "1000 + 1 = 1001"
+    );
+    assert_eq!(
+        run(&[], &["--locale", "de", "add", "1500m", "1km"]),
+        "1500m + 1km = 2.500 m"
+    );
+}
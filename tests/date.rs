@@ -0,0 +1,158 @@
+mod common;
+
+use argh_demo::commands::date::{self, DateAddOptions, DateCommand, DateOptions};
+use argh_demo::date::{Date, Instant, Offset};
+use argh_demo::output::{Format, Notation, Printer};
+use common::Sandbox;
+
+fn date(text: &str) -> Date {
+    text.parse().unwrap()
+}
+
+fn add(options: DateAddOptions) -> Result<(), argh_demo::CalcError> {
+    let mut printer = Printer::new(Format::Bare, Notation::default());
+    date::execute(
+        DateOptions {
+            command: DateCommand::Add(options),
+        },
+        &mut printer,
+    )
+}
+
+#[test]
+fn adds_days_and_months() {
+    assert_eq!(date("2026-10-17").add_days(15), date("2026-11-01"));
+    assert_eq!(date("2024-03-01").add_days(-1), date("2024-02-29"));
+    assert_eq!(date("2026-01-31").add_months(1), date("2026-02-28"));
+    assert_eq!(date("2024-01-31").add_months(1), date("2024-02-29"));
+    assert_eq!(date("2026-03-15").add_months(-15), date("2024-12-15"));
+}
+
+#[test]
+fn adds_business_days() {
+    // 2026-10-16 is a Friday.
+    assert_eq!(date("2026-10-16").add_business_days(1), date("2026-10-19"));
+    assert_eq!(date("2026-10-19").add_business_days(-1), date("2026-10-16"));
+    assert_eq!(date("2026-10-16").add_business_days(10), date("2026-10-30"));
+    // From a weekend, Saturday counts as Friday going forward and Sunday
+    // as Monday going back.
+    assert_eq!(date("2026-10-17").add_business_days(5), date("2026-10-23"));
+    assert_eq!(date("2026-10-18").add_business_days(-1), date("2026-10-16"));
+}
+
+#[test]
+fn diffs_dates() {
+    let from = date("2026-01-31");
+    assert_eq!(from.months_until(date("2026-02-28")), 1);
+    assert_eq!(from.months_until(date("2026-02-27")), 0);
+    assert_eq!(date("2026-03-31").months_until(date("2026-01-31")), -2);
+    assert_eq!(
+        date("2026-10-16").business_days_until(date("2026-10-23")),
+        5
+    );
+    assert_eq!(
+        date("2026-10-23").business_days_until(date("2026-10-16")),
+        -5
+    );
+    assert_eq!(
+        date("2026-10-17").business_days_until(date("2026-10-19")),
+        0
+    );
+}
+
+#[test]
+fn names_the_unit_of_a_difference() {
+    let sandbox = Sandbox::new("date");
+    let diff = |args: &[&str]| {
+        let all = [&["--no-history", "date", "diff"][..], args].concat();
+        sandbox.stdout(&all)
+    };
+    assert_eq!(
+        diff(&["2024-03-01", "2024-01-01"]),
+        "2024-01-01 - 2024-03-01 = -60 days\n"
+    );
+    assert_eq!(
+        diff(&["2024-01-01", "2024-01-02"]),
+        "2024-01-02 - 2024-01-01 = 1 day\n"
+    );
+    assert_eq!(
+        diff(&["2026-10-16", "2026-10-23", "--in", "business-days"]),
+        "2026-10-23 - 2026-10-16 = 5 business days\n"
+    );
+    assert_eq!(
+        diff(&["2026-01-31", "2026-02-28", "--in", "months"]),
+        "2026-02-28 - 2026-01-31 = 1 month\n"
+    );
+}
+
+#[test]
+fn numbers_iso_weeks() {
+    assert_eq!(date("2026-10-17").iso_week(), (2026, 42));
+    // The first Thursday decides week 1, so early January can belong to
+    // the year before and late December to the year after.
+    assert_eq!(date("2021-01-03").iso_week(), (2020, 53));
+    assert_eq!(date("2024-12-30").iso_week(), (2025, 1));
+    assert_eq!(date("2026-01-01").iso_week(), (2026, 1));
+    assert_eq!(date("2026-10-17").weekday(), 6);
+    assert_eq!(date("2024-12-31").ordinal(), 366);
+}
+
+#[test]
+fn converts_instants() {
+    let time: Instant = "2026-10-17T12:30:00+02:00".parse().unwrap();
+    assert_eq!(time.timestamp(), 1_792_233_000);
+    assert_eq!(time.to_string(), "2026-10-17T12:30:00+02:00");
+    let utc = Instant::from_timestamp(0, Offset::default()).unwrap();
+    assert_eq!(utc.to_string(), "1970-01-01T00:00:00Z");
+}
+
+#[test]
+fn refuses_times_outside_four_digit_years() {
+    let east: Offset = "+14:00".parse().unwrap();
+    assert!(Instant::from_timestamp(i64::MAX, east).is_none());
+    assert!(Instant::from_timestamp(i64::MIN, Offset::default()).is_none());
+    assert!(Instant::from_timestamp(253_402_300_799, Offset::default()).is_some());
+    assert!(Instant::from_timestamp(253_402_300_799, east).is_none());
+    assert!(Instant::from_timestamp(-62_167_219_200, Offset::default()).is_some());
+    assert!(Instant::from_timestamp(-62_167_219_201, Offset::default()).is_none());
+}
+
+#[test]
+fn refuses_moves_outside_four_digit_years() {
+    let start = Some(date("2026-01-01"));
+    for options in [
+        DateAddOptions {
+            business_days: i64::MIN,
+            date: start,
+            ..Default::default()
+        },
+        DateAddOptions {
+            months: i64::MIN,
+            date: start,
+            ..Default::default()
+        },
+        DateAddOptions {
+            years: i64::MAX,
+            date: start,
+            ..Default::default()
+        },
+        DateAddOptions {
+            years: 8000,
+            date: start,
+            ..Default::default()
+        },
+        DateAddOptions {
+            days: -740_000,
+            date: start,
+            ..Default::default()
+        },
+    ] {
+        assert!(add(options).is_err());
+    }
+    assert!(add(DateAddOptions {
+        years: 7973,
+        date: start,
+        ..Default::default()
+    })
+    .is_ok());
+}
@@ -0,0 +1,66 @@
+use std::fs;
+use std::path::Path;
+
+use argh_demo::docs;
+use argh_demo::help;
+
+fn check(file: &str, generated: String) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(file);
+    let checked_in = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("cannot read {}: {}", path.display(), err));
+    assert!(
+        checked_in == generated,
+        "{} is stale; run `cargo run -- gen-docs` and commit the result",
+        file
+    );
+}
+
+#[test]
+fn man_page_is_up_to_date() {
+    check(docs::MAN_PAGE, docs::man_page());
+}
+
+#[test]
+fn reference_is_up_to_date() {
+    check(docs::REFERENCE, docs::reference());
+}
+
+#[test]
+fn readme_usage_is_up_to_date() {
+    for file in docs::READMES {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(file);
+        let readme = fs::read_to_string(&path).unwrap();
+        let updated =
+            docs::readme(&readme).unwrap_or_else(|| panic!("{} has no usage markers", file));
+        check(file, updated);
+    }
+}
+
+#[test]
+fn docs_cover_every_command() {
+    let man_page = docs::man_page();
+    let reference = docs::reference();
+    for command in &help::command_tree().walk()[1..] {
+        let title = command.path.join(" ");
+        assert!(
+            reference.contains(&format!("\n## {}\n", title)),
+            "{} is missing from the reference",
+            title
+        );
+        assert!(
+            man_page.contains(&format!(".SS \"{}\"", title.replace('-', "\\-"))),
+            "{} is missing from the man page",
+            title
+        );
+    }
+}
+
+#[test]
+fn gen_docs_is_hidden() {
+    let tree = help::command_tree();
+    assert!(tree
+        .walk()
+        .iter()
+        .all(|command| command.name() != "gen-docs"));
+    assert!(!docs::reference().contains("## argh-demo gen-docs"));
+}
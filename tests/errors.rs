@@ -0,0 +1,49 @@
+mod common;
+
+use argh_demo::ops::{self, Operation, Overflow};
+use argh_demo::{CalcError, Rounding};
+use common::Sandbox;
+
+fn apply<T: argh_demo::Number + std::fmt::Debug>(op: Operation, lhs: T, rhs: T) -> CalcError {
+    ops::apply(op, &lhs, &rhs, Overflow::Checked, &Rounding::default()).unwrap_err()
+}
+
+#[test]
+fn names_the_zero_divisor() {
+    let err = apply(Operation::Div, 7i64, 0);
+    assert_eq!(err.to_string(), "7 / 0 divides by zero");
+    assert_eq!(err.exit_code(), 4);
+    assert_eq!(
+        apply(Operation::Rem, 7.5f64, 0.0).to_string(),
+        "7.5 % 0 divides by zero"
+    );
+}
+
+#[test]
+fn says_when_zero_is_raised_to_a_negative_power() {
+    let err = apply(Operation::Pow, 0.0f64, -1.0);
+    assert_eq!(err.to_string(), "0 ^ -1 raises zero to a negative power");
+    assert_eq!(err.exit_code(), 4);
+    assert!(matches!(err, CalcError::DivisionByZero { rhs, .. } if rhs == "-1"));
+}
+
+#[test]
+fn reports_bad_arguments_on_stderr() {
+    let sandbox = Sandbox::new("errors");
+    let output = sandbox.run(&["add", "--bogus"]);
+    assert_eq!(output.code, 1);
+    assert_eq!(output.stdout, "");
+    assert_eq!(
+        output.stderr,
+        "Unrecognized argument: --bogus\nRun argh-demo --help for more information.\n"
+    );
+
+    let help = sandbox.run(&["add", "--help"]);
+    assert_eq!(help.code, 0);
+    assert!(
+        help.stdout.starts_with("Usage: argh-demo add "),
+        "{}",
+        help.stdout
+    );
+    assert_eq!(help.stderr, "");
+}
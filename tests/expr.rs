@@ -0,0 +1,66 @@
+use argh_demo::expr::Expression;
+use argh_demo::{CalcError, Overflow, Rounding};
+
+fn eval(source: &str) -> Result<i64, CalcError> {
+    let (value, _) = Expression::parse(source)?.eval(Overflow::Checked, &Rounding::default())?;
+    Ok(value)
+}
+
+#[test]
+fn follows_precedence_and_associativity() {
+    assert_eq!(eval("1 + 2 * 3").unwrap(), 7);
+    assert_eq!(eval("(1 + 2) * 3").unwrap(), 9);
+    assert_eq!(eval("10 - 4 - 3").unwrap(), 3);
+    assert_eq!(eval("64 / 4 / 2").unwrap(), 8);
+    assert_eq!(eval("2 ^ 3 ^ 2").unwrap(), 512);
+    assert_eq!(eval("-2 ^ 2").unwrap(), -4);
+    assert_eq!(eval("(-2) ^ 2").unwrap(), 4);
+    assert_eq!(eval("2 * -3").unwrap(), -6);
+    assert_eq!(eval("-9223372036854775808").unwrap(), i64::MIN);
+}
+
+#[test]
+fn points_at_syntax_errors() {
+    let err = eval("1 + * 2").unwrap_err();
+    assert!(
+        matches!(err, CalcError::Syntax { column: 5, .. }),
+        "{:?}",
+        err
+    );
+    assert_eq!(err.exit_code(), 2);
+    let message = err.to_string();
+    let lines: Vec<&str> = message.lines().collect();
+    assert_eq!(lines[1], "  1 + * 2");
+    assert_eq!(lines[2], "      ^");
+
+    let err = eval("(1 + 2").unwrap_err();
+    assert!(
+        matches!(err, CalcError::Syntax { column: 1, .. }),
+        "{:?}",
+        err
+    );
+}
+
+#[test]
+fn limits_nesting() {
+    let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+    assert_eq!(eval(&nested(255)).unwrap(), 1);
+    assert_eq!(eval(&"-".repeat(254)).unwrap_err().exit_code(), 2);
+    assert_eq!(eval(&format!("{}1", "-".repeat(254))).unwrap(), 1);
+    assert_eq!(eval(&vec!["1"; 200].join("+")).unwrap(), 200);
+
+    // Each of these would overflow the stack without the limit.
+    for source in [
+        nested(100_000),
+        format!("{}1", "-".repeat(200_000)),
+        vec!["1"; 300_000].join("+"),
+        format!("{}2", "2^".repeat(100_000)),
+    ] {
+        let err = eval(&source).unwrap_err();
+        assert!(
+            matches!(&err, CalcError::Syntax { message, .. } if message.contains("nested")),
+            "{:?}",
+            err
+        );
+    }
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use std::fs;
+
+use common::Sandbox;
+
+fn formatted(format: &str, args: &[&str]) -> String {
+    let sandbox = Sandbox::new("formats");
+    let mut all = vec!["--no-history", "--format", format];
+    all.extend_from_slice(args);
+    sandbox.stdout(&all)
+}
+
+#[test]
+fn writes_a_header_before_the_rows() {
+    assert_eq!(
+        formatted("csv", &["add", "1", "2"]),
+        "operation,operands,result,type,overflow\nadd,1 2,3,i64,false\n"
+    );
+    assert_eq!(
+        formatted(
+            "tsv",
+            &["--type", "f64", "mul", "--num1", "1.5", "--num2", "2"]
+        ),
+        "operation\toperands\tresult\ttype\toverflow\nmul\t1.5 2\t3\tf64\tfalse\n"
+    );
+    assert_eq!(
+        formatted("json", &["add", "1", "2"]),
+        "{\"operation\":\"add\",\"operands\":[\"1\",\"2\"],\"result\":\"3\",\"type\":\"i64\",\"overflow\":false}\n"
+    );
+}
+
+#[test]
+fn flags_overflow_in_every_format() {
+    let wrapped = ["--type", "u8", "add", "250", "10", "--wrapping"];
+    assert_eq!(
+        formatted("csv", &wrapped),
+        "operation,operands,result,type,overflow\nadd,250 10,4,u8,true\n"
+    );
+    assert_eq!(
+        formatted("tsv", &wrapped),
+        "operation\toperands\tresult\ttype\toverflow\nadd\t250 10\t4\tu8\ttrue\n"
+    );
+    assert!(formatted("json", &wrapped).ends_with(",\"overflow\":true}\n"));
+}
+
+#[test]
+fn escapes_separators_in_fields() {
+    let expression = ["eval", "1 +\n\t2"];
+    assert_eq!(
+        formatted("csv", &expression),
+        "operation,operands,result,type,overflow\neval,\"1 +\n\t2\",3,i64,false\n"
+    );
+    // TSV cannot quote, so tabs and newlines become spaces.
+    assert_eq!(
+        formatted("tsv", &expression),
+        "operation\toperands\tresult\ttype\toverflow\neval\t1 +  2\t3\ti64\tfalse\n"
+    );
+    assert_eq!(
+        formatted("json", &expression),
+        "{\"operation\":\"eval\",\"operands\":[\"1 +\\n\\t2\"],\"result\":\"3\",\"type\":\"i64\",\"overflow\":false}\n"
+    );
+}
+
+#[test]
+fn quotes_commas_and_quotes_in_exported_history() {
+    let sandbox = Sandbox::new("formats");
+    let path = sandbox.path().join("a,\"b\".txt");
+    fs::write(&path, "3\n").unwrap();
+    let path = path.to_str().unwrap();
+    assert_eq!(sandbox.stdout(&["add", "1", "--file", path]), "4\n");
+
+    let csv = sandbox.stdout(&["history", "export"]);
+    let row = csv.lines().nth(1).unwrap();
+    assert!(row.starts_with("1,"), "{}", row);
+    assert!(
+        row.ends_with(&format!(
+            ",add,(streamed),4,i64,false,\"argh-demo add 1 --file '{}'\"",
+            path.replace('"', "\"\"")
+        )),
+        "{}",
+        row
+    );
+    let json = sandbox.stdout(&["--format", "json", "history", "export"]);
+    assert!(
+        json.ends_with(&format!(
+            "\"command\":\"argh-demo add 1 --file '{}'\"}}\n",
+            path.replace('"', "\\\"")
+        )),
+        "{}",
+        json
+    );
+}
+
+#[test]
+fn keeps_rows_in_line_with_the_header() {
+    let exact = ["--type", "f64", "add", "0.5", "0.25", "--rounding-error"];
+    assert_eq!(
+        formatted("csv", &exact),
+        "operation,operands,result,type,overflow,rounding_error\nadd,0.5 0.25,0.75,f64,false,0\n"
+    );
+    // Quantities have no rounding error, but the column is still there.
+    assert_eq!(
+        formatted("tsv", &["add", "1m", "2m", "--rounding-error"]),
+        "operation\toperands\tresult\ttype\toverflow\trounding_error\nadd\t1m 2m\t3 m\tquantity\tfalse\t\n"
+    );
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use std::fs;
+
+use argh::FromArgs;
+use argh_demo::cli::DemoCli;
+use common::Sandbox;
+
+/// A sandbox whose history holds `1 + 2 = 3`, `3 * 4 = 12`, then the two
+/// statements of a script.
+fn with_history() -> Sandbox {
+    let sandbox = Sandbox::new("history");
+    sandbox.stdout(&["add", "1", "2"]);
+    sandbox.stdout(&["mul", "--num1", "!1", "--num2", "4"]);
+    let script = sandbox.path().join("two.calc");
+    fs::write(&script, "1 + 1\n2 * 3\n").unwrap();
+    sandbox.stdout(&["run", script.to_str().unwrap()]);
+    sandbox
+}
+
+/// The text column of `history list` in bare form.
+fn listed(sandbox: &Sandbox, args: &[&str]) -> Vec<String> {
+    let mut all = vec!["--format", "bare", "history"];
+    all.extend_from_slice(args);
+    sandbox.stdout(&all).lines().map(String::from).collect()
+}
+
+#[test]
+fn lists_and_searches() {
+    let sandbox = with_history();
+    assert_eq!(
+        listed(&sandbox, &["list"]),
+        ["1 + 2 = 3", "3 * 4 = 12", "1 + 1 = 2", "2 * 3 = 6"]
+    );
+    assert_eq!(
+        listed(&sandbox, &["list", "--last", "2"]),
+        ["1 + 1 = 2", "2 * 3 = 6"]
+    );
+    assert_eq!(
+        listed(&sandbox, &["search", "*"]),
+        ["3 * 4 = 12", "2 * 3 = 6"]
+    );
+    assert_eq!(listed(&sandbox, &["search", "mul"]), ["3 * 4 = 12"]);
+    assert!(listed(&sandbox, &["search", "nothing"]).is_empty());
+
+    let plain = sandbox.stdout(&["history", "list"]);
+    let first = plain.lines().next().unwrap();
+    assert!(first.starts_with("    1  "), "{}", first);
+    assert!(first.ends_with("Z  1 + 2 = 3"), "{}", first);
+}
+
+#[test]
+fn shows_one_entry() {
+    let sandbox = with_history();
+    let shown = sandbox.stdout(&["--format", "json", "history", "show", "2"]);
+    assert!(shown.starts_with(r#"{"id":"2","time":"#), "{}", shown);
+    assert!(
+        shown.ends_with(concat!(
+            r#""operation":"mul","operands":"3 4","result":"12","type":"i64","#,
+            r#""overflow":"false","command":"argh-demo mul --num1 '!1' --num2 4"}"#,
+            "\n"
+        )),
+        "{}",
+        shown
+    );
+    let missing = sandbox.run(&["history", "show", "9"]);
+    assert_eq!(missing.code, 2);
+    assert_eq!(missing.stderr, "error: there is no history entry 9\n");
+}
+
+#[test]
+fn exports_every_entry() {
+    let sandbox = with_history();
+    let csv = sandbox.stdout(&["history", "export"]);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(
+        lines[0],
+        "id,time,operation,operands,result,type,overflow,command"
+    );
+    assert!(lines[3].starts_with("3,"), "{}", lines[3]);
+    assert!(lines[3].contains(",eval,1 + 1,2,i64,false,argh-demo run "));
+    let tsv = sandbox.stdout(&["--format", "tsv", "history", "export"]);
+    assert_eq!(tsv.lines().nth(1).unwrap().split('\t').count(), 8);
+}
+
+#[test]
+fn saves_each_run_at_once() {
+    let sandbox = with_history();
+    let file = sandbox.path().join("data/argh-demo/calculations");
+    assert_eq!(fs::read_to_string(&file).unwrap().lines().count(), 4);
+    sandbox.stdout(&["--no-history", "add", "5", "5"]);
+    assert_eq!(fs::read_to_string(&file).unwrap().lines().count(), 4);
+    // A failing run keeps what it printed before the error.
+    let script = sandbox.path().join("bad.calc");
+    fs::write(&script, "7 * 7\n1 / 0\n").unwrap();
+    let failed = sandbox.run(&["run", "--keep-going", script.to_str().unwrap()]);
+    assert_eq!(failed.code, 4);
+    assert_eq!(listed(&sandbox, &["list", "--last", "1"]), ["7 * 7 = 49"]);
+}
+
+#[test]
+fn clears() {
+    let sandbox = with_history();
+    sandbox.stdout(&["history", "clear"]);
+    assert!(listed(&sandbox, &["list"]).is_empty());
+    sandbox.stdout(&["history", "clear"]);
+    let recalled = sandbox.run(&["add", "!1", "1"]);
+    assert_eq!(recalled.code, 2);
+    assert_eq!(recalled.stderr, "error: there is no history entry 1\n");
+}
+
+#[test]
+fn reuses_results() {
+    let sandbox = with_history();
+    assert_eq!(sandbox.stdout(&["add", "!2", "!4"]), "12 + 6 = 18\n");
+    assert_eq!(
+        sandbox.stdout(&["sub", "--from-history", "2", "2"]),
+        "12 - 2 = 10\n"
+    );
+    assert_eq!(sandbox.stdout(&["!1"]), "1 + 2 = 3\n");
+}
+
+#[test]
+fn looks_up_references_after_parsing() {
+    // Parsing alone must not read the history or the registers, which
+    // may not exist.
+    assert!(DemoCli::from_args(&["argh-demo"], &["add", "!999", "@nothing"]).is_ok());
+    assert!(DemoCli::from_args(&["argh-demo"], &["add", "!x", "1"]).is_err());
+}
+
+#[test]
+fn keeps_results_shown_their_own_way() {
+    let sandbox = Sandbox::new("history");
+    let repl = sandbox.run_input(&[], &["repl"], "1 + 2\nx = 4\n:vars\n");
+    assert_eq!(repl.stdout, "3\nx = 4\nans = 4\nx = 4\n");
+    sandbox.stdout(&["bit", "and", "--num1", "6", "--num2", "3", "--show-bits"]);
+    sandbox.stdout(&["stats", "mean", "1", "2", "3"]);
+    sandbox.stdout(&["stats", "percentile", "--p", "50", "1", "2", "3", "4"]);
+    sandbox.stdout(&["stats", "mode", "1", "1", "2"]);
+    sandbox.stdout(&["stats", "histogram", "1", "2"]);
+    assert_eq!(
+        listed(&sandbox, &["list"]),
+        ["1 + 2 = 3", "x = 4 = 4", "6 & 3 = 2", "2", "2.5"]
+    );
+    let shown = sandbox.stdout(&["--format", "bare", "history", "show", "5"]);
+    assert!(
+        shown.contains("percentile\n(streamed)\n2.5\nf64\n"),
+        "{}",
+        shown
+    );
+}
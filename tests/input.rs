@@ -0,0 +1,84 @@
+mod common;
+
+use std::fs;
+
+use argh_demo::CalcError;
+use common::Sandbox;
+
+fn add(sandbox: &Sandbox, args: &[&str], input: &str) -> common::Output {
+    let mut all = vec!["--no-history", "--format", "bare", "add"];
+    all.extend_from_slice(args);
+    sandbox.run_input(&[], &all, input)
+}
+
+#[test]
+fn streams_stdin() {
+    let sandbox = Sandbox::new("input");
+    let output = add(&sandbox, &["--stdin"], "1\n2\n\n 3 4,5\r\n");
+    assert_eq!(output.code, 0, "{}", output.stderr);
+    assert_eq!(output.stdout, "15\n");
+    // `-` names stdin among the files.
+    let output = add(&sandbox, &["10", "--file", "-"], "5\n");
+    assert_eq!(output.stdout, "15\n");
+}
+
+#[test]
+fn reads_operands_then_files_then_stdin() {
+    let sandbox = Sandbox::new("input");
+    let path = sandbox.path().join("numbers.txt");
+    fs::write(&path, "1\n2\n").unwrap();
+    let path = path.to_str().unwrap();
+    let all = ["--no-history", "sub", "100", "--stdin", "--file", path];
+    let output = sandbox.run_input(&[], &all, "7\n");
+    assert_eq!(output.stdout, "90\n");
+    // Streamed operands are not listed in the record.
+    let json = sandbox.run_input(
+        &[],
+        &["--no-history", "--format", "json", "add", "--stdin"],
+        "1\n2\n",
+    );
+    assert_eq!(
+        json.stdout,
+        "{\"operation\":\"add\",\"operands\":null,\"result\":\"3\",\"type\":\"i64\",\"overflow\":false}\n"
+    );
+}
+
+#[test]
+fn names_the_line_of_an_invalid_value() {
+    let sandbox = Sandbox::new("input");
+    let path = sandbox.path().join("mixed.txt");
+    fs::write(&path, "1\n2\nabc\n4\n").unwrap();
+    let path = path.to_str().unwrap();
+    let output = add(&sandbox, &["--file", path], "");
+    let invalid = CalcError::InvalidInput {
+        source: String::new(),
+        line: 0,
+        text: String::new(),
+        ty: "i64",
+        reason: String::new(),
+    };
+    assert_eq!(output.code, invalid.exit_code());
+    assert_eq!(output.stdout, "");
+    assert_eq!(
+        output.stderr,
+        format!(
+            "error: {}:3: `abc` is not a valid i64: invalid digit found in string\n",
+            path
+        )
+    );
+
+    let output = add(&sandbox, &["--stdin"], "1\n2 x\n");
+    assert_eq!(output.code, invalid.exit_code());
+    assert_eq!(
+        output.stderr,
+        "error: <stdin>:2: `x` is not a valid i64: invalid digit found in string\n"
+    );
+}
+
+#[test]
+fn refuses_a_missing_file() {
+    let sandbox = Sandbox::new("input");
+    let output = add(&sandbox, &["1", "--file", "missing.txt"], "");
+    assert_eq!(output.code, 5);
+    assert!(output.stderr.starts_with("error: cannot read missing.txt"));
+}
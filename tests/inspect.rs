@@ -0,0 +1,50 @@
+mod common;
+
+use common::Sandbox;
+
+/// The value and class `inspect` reports for `num`.
+fn inspect(ty: &str, num: &str) -> (String, String) {
+    let sandbox = Sandbox::new("inspect");
+    let out = sandbox.stdout(&[
+        "--no-history",
+        "--type",
+        ty,
+        "--format",
+        "bare",
+        "inspect",
+        "--num",
+        num,
+    ]);
+    let lines: Vec<&str> = out.lines().collect();
+    (lines[0].to_string(), lines[2].to_string())
+}
+
+#[test]
+fn reads_special_values() {
+    assert_eq!(inspect("f64", "nan"), ("NaN".into(), "nan".into()));
+    assert_eq!(inspect("f64", "inf"), ("inf".into(), "infinite".into()));
+    assert_eq!(inspect("f64", "-inf"), ("-inf".into(), "infinite".into()));
+    assert_eq!(inspect("f32", "NaN"), ("NaN".into(), "nan".into()));
+    assert_eq!(inspect("f64", "-0"), ("-0".into(), "zero".into()));
+}
+
+#[test]
+fn keeps_ieee_results_of_expressions() {
+    assert_eq!(inspect("f64", "0/0"), ("NaN".into(), "nan".into()));
+    assert_eq!(inspect("f64", "1/0"), ("inf".into(), "infinite".into()));
+    assert_eq!(inspect("f64", "-1/0"), ("-inf".into(), "infinite".into()));
+    assert_eq!(
+        inspect("f32", "1e38 * 10"),
+        ("inf".into(), "infinite".into())
+    );
+    assert_eq!(inspect("f64", "0.1 + 0.2").1, "normal");
+}
+
+#[test]
+fn classifies_subnormals() {
+    let (value, class) = inspect("f64", "5e-324");
+    assert_eq!(class, "subnormal");
+    assert_eq!(value.parse::<f64>().unwrap(), f64::from_bits(1));
+    assert_eq!(inspect("f64", "2.2250738585072014e-308").1, "normal");
+    assert_eq!(inspect("f32", "1e-45").1, "subnormal");
+}
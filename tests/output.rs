@@ -0,0 +1,132 @@
+mod common;
+
+use common::Sandbox;
+
+fn plain(args: &[&str]) -> String {
+    let sandbox = Sandbox::new("output");
+    let mut all = vec!["--no-history"];
+    all.extend_from_slice(args);
+    sandbox.stdout(&all).trim_end().to_string()
+}
+
+#[test]
+fn prints_only_integers_in_another_base() {
+    assert_eq!(
+        plain(&["--output-base", "16", "add", "10", "20"]),
+        "a + 14 = 1e"
+    );
+    assert_eq!(
+        plain(&[
+            "--type",
+            "f64",
+            "--output-base",
+            "16",
+            "add",
+            "--num1",
+            "0.5",
+            "--num2",
+            "0.5"
+        ]),
+        "0.5 + 0.5 = 1"
+    );
+    assert_eq!(
+        plain(&[
+            "--type",
+            "rational",
+            "--output-base",
+            "2",
+            "add",
+            "1/2",
+            "3/2"
+        ]),
+        "1/2 + 3/2 = 10"
+    );
+
+    let sandbox = Sandbox::new("output");
+    let out = sandbox.run(&[
+        "--no-history",
+        "--type",
+        "rational",
+        "--output-base",
+        "16",
+        "add",
+        "1/2",
+        "1/3",
+    ]);
+    assert_eq!(out.code, 2);
+    assert!(
+        out.stderr.contains("only integer results"),
+        "{}",
+        out.stderr
+    );
+}
+
+#[test]
+fn styles_rational_results_but_not_operands() {
+    let rational = |args: &[&str]| {
+        let mut all = vec!["--type", "rational"];
+        all.extend_from_slice(args);
+        plain(&all)
+    };
+    assert_eq!(
+        rational(&["--as-decimal", "3", "div", "--num1", "1", "--num2", "3"]),
+        "1 / 3 = 0.333"
+    );
+    assert_eq!(
+        rational(&["--as-decimal", "2", "add", "1/8", "1/8"]),
+        "1/8 + 1/8 = 0.25"
+    );
+    assert_eq!(
+        rational(&["--as-decimal", "0", "add", "1/2", "1/3"]),
+        "1/2 + 1/3 = 1"
+    );
+    assert_eq!(rational(&["--mixed", "add", "7/4", "1"]), "7/4 + 1 = 2 3/4");
+    assert_eq!(
+        rational(&["--mixed", "sub", "1", "11/4"]),
+        "1 - 11/4 = -1 3/4"
+    );
+    assert_eq!(
+        rational(&["--mixed", "add", "1/4", "1/4"]),
+        "1/4 + 1/4 = 1/2"
+    );
+    assert_eq!(
+        rational(&[
+            "--as-decimal",
+            "3",
+            "--format",
+            "json",
+            "div",
+            "--num1",
+            "1",
+            "--num2",
+            "3"
+        ]),
+        r#"{"operation":"div","operands":["1","3"],"result":"0.333","type":"rational","overflow":false}"#
+    );
+}
+
+#[test]
+fn pads_and_groups_digits() {
+    assert_eq!(
+        plain(&[
+            "--output-base",
+            "16",
+            "--pad",
+            "4",
+            "--group",
+            "2",
+            "add",
+            "10",
+            "20"
+        ]),
+        "00_0a + 00_14 = 00_1e"
+    );
+    assert_eq!(
+        plain(&["--pad", "3", "sub", "--num1", "1", "--num2", "8"]),
+        "001 - 008 = -007"
+    );
+    // Longer than a format width can be.
+    let padded = plain(&["--format", "bare", "--pad", "70000", "add", "1", "2"]);
+    assert_eq!(padded.len(), 70_000);
+    assert!(padded.ends_with("0003"));
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::{Output, Sandbox};
+
+fn run(args: &[&str]) -> Output {
+    let sandbox = Sandbox::new("overflow");
+    let mut all = vec!["--no-history"];
+    all.extend_from_slice(args);
+    sandbox.run(&all)
+}
+
+fn plain(args: &[&str]) -> String {
+    let output = run(args);
+    assert_eq!(output.code, 0, "{:?}: {}", args, output.stderr);
+    output.stdout.trim_end().to_string()
+}
+
+#[test]
+fn fails_on_checked_overflow() {
+    let cases: [&[&str]; 4] = [
+        &["--type", "i8", "add", "100", "100"],
+        &["--type", "u8", "sub", "1", "2"],
+        &["add", "9223372036854775807", "1"],
+        &["mul", "--num1", "9223372036854775807", "--num2", "2"],
+    ];
+    for args in cases {
+        let output = run(args);
+        assert_eq!(output.code, 3, "{:?}", args);
+        assert!(
+            output
+                .stderr
+                .ends_with("(use --wrapping or --saturating to allow it)\n"),
+            "{}",
+            output.stderr
+        );
+    }
+    assert_eq!(
+        run(&["--type", "i8", "add", "100", "100"]).stderr,
+        "error: 100 + 100 overflows i8 (use --wrapping or --saturating to allow it)\n"
+    );
+}
+
+#[test]
+fn wraps_and_saturates() {
+    let cases = [
+        (&["--type", "i8", "add", "100", "100"][..], "-56", "127"),
+        (
+            &["--type", "i8", "sub", "--num1", "-100", "--num2", "100"],
+            "56",
+            "-128",
+        ),
+        (&["--type", "u8", "add", "200", "100"], "44", "255"),
+        (&["--type", "u8", "sub", "1", "2"], "255", "0"),
+        (
+            &["add", "9223372036854775807", "1"],
+            "-9223372036854775808",
+            "9223372036854775807",
+        ),
+        (
+            &["sub", "--num1", "-9223372036854775808", "--num2", "1"],
+            "9223372036854775807",
+            "-9223372036854775808",
+        ),
+    ];
+    for (args, wrapped, saturated) in cases {
+        let wrapping = [&["--format", "bare"][..], args, &["--wrapping"]].concat();
+        assert_eq!(plain(&wrapping), wrapped, "{:?}", args);
+        let saturating = [&["--format", "bare"][..], args, &["--saturating"]].concat();
+        assert_eq!(plain(&saturating), saturated, "{:?}", args);
+    }
+}
+
+#[test]
+fn divides_the_minimum_by_minus_one() {
+    let args = ["--type", "i8", "div", "--num1", "-128", "--num2", "-1"];
+    let output = run(&args);
+    assert_eq!(output.code, 3);
+    assert_eq!(
+        output.stderr,
+        "error: -128 / -1 overflows i8 (use --wrapping or --saturating to allow it)\n"
+    );
+    assert_eq!(
+        plain(&[&args[..], &["--wrapping"]].concat()),
+        "-128 / -1 = -128"
+    );
+    assert_eq!(
+        plain(&[&args[..], &["--saturating"]].concat()),
+        "-128 / -1 = 127"
+    );
+}
+
+#[test]
+fn flags_overflowed_results() {
+    assert_eq!(
+        plain(&[
+            "--format",
+            "json",
+            "--type",
+            "i8",
+            "add",
+            "100",
+            "100",
+            "--wrapping"
+        ]),
+        r#"{"operation":"add","operands":["100","100"],"result":"-56","type":"i8","overflow":true}"#
+    );
+    assert!(plain(&[
+        "--format",
+        "json",
+        "--type",
+        "i8",
+        "add",
+        "1",
+        "2",
+        "--wrapping"
+    ])
+    .ends_with(r#""overflow":false}"#));
+    let output = run(&["add", "1", "2", "--wrapping", "--saturating"]);
+    assert_eq!(output.code, 2);
+    assert_eq!(
+        output.stderr,
+        "error: --wrapping and --saturating cannot be used together\n"
+    );
+}
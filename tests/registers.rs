@@ -0,0 +1,81 @@
+mod common;
+
+use std::thread;
+
+use common::Sandbox;
+
+#[test]
+fn stores_and_recalls() {
+    let sandbox = Sandbox::new("registers");
+    assert_eq!(sandbox.stdout(&["store", "x", "5"]), "x:  5\n");
+    assert_eq!(sandbox.stdout(&["store", "--num", "0x10", "y"]), "y:  16\n");
+    assert_eq!(sandbox.stdout(&["store", "trip", "3km"]), "trip:  3km\n");
+    assert_eq!(sandbox.stdout(&["recall", "x"]), "x:  5\n");
+    assert_eq!(
+        sandbox.stdout(&["recall"]),
+        "trip:  3km\nx:     5\ny:     16\n"
+    );
+    assert_eq!(
+        sandbox.stdout(&["--format", "json", "recall"]),
+        "{\"trip\":\"3km\",\"x\":\"5\",\"y\":\"16\"}\n"
+    );
+    assert_eq!(sandbox.stdout(&["store", "x", "--add", "2"]), "x:  7\n");
+    assert_eq!(sandbox.stdout(&["store", "x", "--sub", "10"]), "x:  -3\n");
+    assert_eq!(
+        sandbox.stdout(&["--output-base", "16", "store", "h", "255"]),
+        "h:  ff\n"
+    );
+}
+
+#[test]
+fn uses_registers_as_operands() {
+    let sandbox = Sandbox::new("registers");
+    sandbox.stdout(&["store", "x", "5"]);
+    sandbox.stdout(&["store", "trip", "3km"]);
+    assert_eq!(sandbox.stdout(&["add", "@x", "1"]), "5 + 1 = 6\n");
+    assert_eq!(
+        sandbox.stdout(&["mul", "--num1", "@x", "--num2", "@x"]),
+        "5 * 5 = 25\n"
+    );
+    assert_eq!(
+        sandbox.stdout(&["add", "@trip", "500m"]),
+        "3km + 500m = 3.5 km\n"
+    );
+    assert_eq!(
+        sandbox.stdout(&["convert", "--num", "@trip", "--to", "m"]),
+        "3 km = 3000 m\n"
+    );
+    assert_eq!(sandbox.stdout(&["store", "y", "@x"]), "y:  5\n");
+}
+
+#[test]
+fn refuses_bad_names_and_values() {
+    let sandbox = Sandbox::new("registers");
+    let missing = sandbox.run(&["add", "@nothing", "1"]);
+    assert_eq!(missing.code, 2);
+    assert_eq!(missing.stderr, "error: there is no register `nothing`\n");
+    assert_eq!(sandbox.run(&["recall", "nothing"]).code, 2);
+    let bad = sandbox.run(&["store", "1x", "3"]);
+    assert_eq!(bad.code, 2);
+    assert!(
+        bad.stderr.contains("is not a register name"),
+        "{}",
+        bad.stderr
+    );
+    assert_eq!(sandbox.run(&["store", "x", "seven"]).code, 2);
+    assert_eq!(sandbox.run(&["store", "x", "--num", "1", "2"]).code, 2);
+    assert_eq!(sandbox.run(&["store", "x", "1km", "--add"]).code, 2);
+    // Nothing was stored by the failed runs.
+    assert_eq!(sandbox.stdout(&["recall"]), "");
+}
+
+#[test]
+fn adds_concurrently_without_losing_updates() {
+    let sandbox = Sandbox::new("registers");
+    thread::scope(|scope| {
+        for _ in 0..16 {
+            scope.spawn(|| sandbox.stdout(&["store", "n", "--add", "1"]));
+        }
+    });
+    assert_eq!(sandbox.stdout(&["recall", "n"]), "n:  16\n");
+}
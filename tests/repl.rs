@@ -0,0 +1,34 @@
+mod common;
+
+use common::Sandbox;
+
+fn repl(sandbox: &Sandbox, input: &str) -> String {
+    let output = sandbox.run_input(&[], &["repl"], input);
+    assert_eq!(output.code, 0, "{}", output.stderr);
+    output.stdout
+}
+
+#[test]
+fn evaluates_lines_and_keeps_variables() {
+    let sandbox = Sandbox::new("repl");
+    assert_eq!(
+        repl(&sandbox, "x = 2 * 3\nx + 1\nans * 2\n:vars\n"),
+        "x = 6\n7\n14\nans = 14\nx = 6\n"
+    );
+}
+
+#[test]
+fn lists_lines_from_earlier_sessions() {
+    let sandbox = Sandbox::new("repl");
+    repl(&sandbox, "1 + 2\nx = 4\n");
+    // Variables do not outlive a session, but the lines entered do.
+    let output = sandbox.run_input(&[], &["repl"], "x\n:history\n");
+    assert!(output.stderr.contains("error:"), "{}", output.stderr);
+    assert_eq!(output.stdout, "    1  1 + 2\n    2  x = 4\n    3  x\n");
+}
+
+#[test]
+fn starts_with_an_empty_history() {
+    let sandbox = Sandbox::new("repl");
+    assert_eq!(repl(&sandbox, ":history\n"), "");
+}
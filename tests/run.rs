@@ -0,0 +1,90 @@
+mod common;
+
+use std::fs;
+
+use common::{Output, Sandbox};
+
+const SCRIPT: &str = "x = 2\n# a comment\n\nx / 0\ny + 1\nx * 3\n";
+
+fn run_script(sandbox: &Sandbox, script: &str, options: &[&str]) -> Output {
+    let path = sandbox.path().join("script.txt");
+    fs::write(&path, script).unwrap();
+    let path = path.to_str().unwrap().to_string();
+    let mut args = vec!["--no-history", "run"];
+    args.extend_from_slice(options);
+    args.push(&path);
+    let mut output = sandbox.run(&args);
+    output.stderr = output.stderr.replace(&path, "script.txt");
+    output
+}
+
+#[test]
+fn stops_at_the_first_failing_line() {
+    let sandbox = Sandbox::new("run");
+    let output = run_script(&sandbox, SCRIPT, &[]);
+    assert_eq!(output.code, 4);
+    assert_eq!(output.stdout, "x = 2 = 2\n");
+    assert_eq!(
+        output.stderr,
+        "error: script.txt:4: 2 / 0 divides by zero\n"
+    );
+}
+
+#[test]
+fn keeps_going_and_counts_failures() {
+    let sandbox = Sandbox::new("run");
+    let output = run_script(&sandbox, SCRIPT, &["--keep-going"]);
+    // The exit code is that of the first failure.
+    assert_eq!(output.code, 4);
+    assert_eq!(output.stdout, "x = 2 = 2\nx * 3 = 6\n");
+    let lines: Vec<&str> = output.stderr.lines().collect();
+    assert_eq!(lines[0], "error: script.txt:4: 2 / 0 divides by zero");
+    assert!(lines[1].starts_with("error: script.txt:5: unknown variable `y`"));
+    assert_eq!(
+        lines.last(),
+        Some(&"error: 2 statements of script.txt failed")
+    );
+}
+
+#[test]
+fn counts_a_single_failure() {
+    let sandbox = Sandbox::new("run");
+    let output = run_script(&sandbox, "1 + 1\nadd 1 x\n", &["--keep-going"]);
+    assert_eq!(output.code, 2);
+    assert!(output.stderr.starts_with("error: script.txt:2: "));
+    assert!(output
+        .stderr
+        .ends_with("error: 1 statement of script.txt failed\n"));
+}
+
+#[test]
+fn names_stdin_in_errors() {
+    let sandbox = Sandbox::new("run");
+    let output = sandbox.run_input(&[], &["--no-history", "run", "--stdin"], "1 +\n");
+    assert_eq!(output.code, 2);
+    assert!(
+        output.stderr.starts_with("error: <stdin>:1: "),
+        "{}",
+        output.stderr
+    );
+
+    let output = sandbox.run_input(
+        &[],
+        &["--no-history", "run", "--stdin"],
+        "add 9223372036854775807 1\n",
+    );
+    assert_eq!(output.code, 3);
+    assert!(
+        output.stderr.starts_with("error: <stdin>:1: "),
+        "{}",
+        output.stderr
+    );
+}
+
+#[test]
+fn reports_a_missing_script() {
+    let sandbox = Sandbox::new("run");
+    let output = sandbox.run(&["--no-history", "run", "missing.txt"]);
+    assert_eq!(output.code, 5);
+    assert!(output.stderr.contains("cannot read missing.txt"));
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use argh_demo::stats::{Moments, Quantile, P2};
+use common::Sandbox;
+
+fn moments(values: &[f64]) -> Moments {
+    let mut moments = Moments::default();
+    for &value in values {
+        moments.push(value);
+    }
+    moments
+}
+
+fn close(actual: f64, expected: f64, tolerance: f64) -> bool {
+    (actual - expected).abs() <= tolerance
+}
+
+#[test]
+fn welford_matches_known_moments() {
+    let moments = moments(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+    assert_eq!(moments.count(), 8);
+    assert_eq!(moments.mean(), Some(5.0));
+    assert_eq!(moments.variance(true), Some(4.0));
+    assert_eq!(moments.variance(false), Some(32.0 / 7.0));
+    assert_eq!(moments.min(), Some(2.0));
+    assert_eq!(moments.max(), Some(9.0));
+}
+
+#[test]
+fn welford_avoids_cancellation() {
+    // The sum-of-squares formula loses every digit of this variance.
+    let offset = 1e9;
+    let moments = moments(&[offset + 4.0, offset + 7.0, offset + 13.0, offset + 16.0]);
+    assert_eq!(moments.mean(), Some(offset + 10.0));
+    assert_eq!(moments.variance(false), Some(30.0));
+}
+
+#[test]
+fn moments_of_nothing() {
+    let empty = Moments::default();
+    assert_eq!(empty.count(), 0);
+    assert_eq!(empty.mean(), None);
+    assert_eq!(empty.variance(true), None);
+    assert_eq!(empty.variance(false), None);
+    assert_eq!(empty.min(), None);
+    assert_eq!(empty.max(), None);
+    assert_eq!(moments(&[3.0]).variance(false), None);
+    assert_eq!(moments(&[3.0]).variance(true), Some(0.0));
+}
+
+#[test]
+fn p2_matches_the_published_example() {
+    // The observations and final median estimate from Jain and Chlamtac,
+    // "The P² algorithm for dynamic calculation of quantiles and
+    // histograms without storing observations", CACM 28(10), 1985.
+    let observations = [
+        0.02, 0.15, 0.74, 3.39, 0.83, 22.37, 10.15, 15.43, 38.62, 15.92, 34.60, 10.28, 1.47, 0.40,
+        0.05, 11.39, 0.27, 0.42, 0.09, 11.37,
+    ];
+    let mut median = P2::new(0.5);
+    for &value in &observations {
+        median.push(value);
+    }
+    let estimate = median.estimate().unwrap();
+    assert!(close(estimate, 4.44, 0.005), "{}", estimate);
+}
+
+#[test]
+fn p2_is_exact_for_few_values() {
+    let mut median = P2::new(0.5);
+    assert_eq!(median.estimate(), None);
+    for &value in &[5.0, 1.0, 3.0, 2.0] {
+        median.push(value);
+    }
+    assert_eq!(median.estimate(), Some(2.5));
+}
+
+#[test]
+fn p2_follows_a_uniform_stream() {
+    let mut quartile = P2::new(0.25);
+    // A fixed permutation of 0..10007, so the stream is not sorted.
+    for i in 0..10_007u64 {
+        quartile.push((i * 7919 % 10_007) as f64);
+    }
+    let estimate = quartile.estimate().unwrap();
+    assert!(close(estimate, 2501.5, 25.0), "{}", estimate);
+}
+
+#[test]
+fn quantiles_are_exact_below_the_limit() {
+    let mut median = Quantile::new(0.5);
+    assert_eq!(median.value(), None);
+    for &value in &[9.0, 1.0, 4.0, 7.0] {
+        median.push(value);
+    }
+    assert_eq!(median.value(), Some((5.5, false)));
+}
+
+#[test]
+fn commands_print_known_results() {
+    let sandbox = Sandbox::new("stats");
+    let data = ["2", "4", "4", "4", "5", "5", "7", "9"];
+    let run = |command: &[&str]| {
+        let mut args = vec!["--no-history", "--format", "bare", "stats"];
+        args.extend_from_slice(command);
+        args.extend_from_slice(&data);
+        sandbox.stdout(&args)
+    };
+    assert_eq!(run(&["mean"]), "5\n");
+    assert_eq!(run(&["median"]), "4.5\n");
+    assert_eq!(run(&["mode"]), "4\n");
+    assert_eq!(run(&["stddev", "--population"]), "2\n");
+    assert_eq!(run(&["variance", "--population"]), "4\n");
+    assert_eq!(run(&["min"]), "2\n");
+    assert_eq!(run(&["max"]), "9\n");
+    assert_eq!(run(&["percentile", "--p", "25"]), "4\n");
+}
+
+#[test]
+fn refuses_empty_input() {
+    let sandbox = Sandbox::new("stats");
+    for command in &["mean", "median", "mode", "stddev", "min", "max"] {
+        let output = sandbox.run_input(&[], &["--no-history", "stats", command, "--stdin"], "");
+        assert_eq!(output.code, 2, "{}", command);
+        assert!(
+            output.stderr.contains("no operands given"),
+            "{}",
+            output.stderr
+        );
+    }
+    let output = sandbox.run(&["--no-history", "stats", "variance", "5"]);
+    assert_eq!(output.code, 2);
+    assert!(output.stderr.contains("needs at least two values"));
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use common::{Output, Sandbox};
+
+fn run(args: &[&str]) -> Output {
+    let sandbox = Sandbox::new("types");
+    let mut all = vec!["--no-history"];
+    all.extend_from_slice(args);
+    sandbox.run(&all)
+}
+
+fn plain(args: &[&str]) -> String {
+    let output = run(args);
+    assert_eq!(output.code, 0, "{:?}: {}", args, output.stderr);
+    output.stdout.trim_end().to_string()
+}
+
+#[test]
+fn refuses_unsigned_underflow() {
+    let output = run(&["--type", "u8", "sub", "3", "5"]);
+    assert_eq!(output.code, 3);
+    assert_eq!(
+        output.stderr,
+        "error: 3 - 5 overflows u8 (use --wrapping or --saturating to allow it)\n"
+    );
+    // The same subtraction is fine in the default i64.
+    assert_eq!(plain(&["sub", "3", "5"]), "3 - 5 = -2");
+    let output = run(&["--type", "u8", "add", "1", "--num", "-1"]);
+    assert_eq!(output.code, 2);
+    assert_eq!(
+        output.stderr,
+        "error: `-1` is not a valid u8: invalid digit found in string\n"
+    );
+}
+
+#[test]
+fn computes_floats_at_their_precision() {
+    assert_eq!(
+        plain(&["--type", "f32", "div", "--num1", "1", "--num2", "3"]),
+        "1 / 3 = 0.33333334"
+    );
+    assert_eq!(
+        plain(&["--type", "f64", "div", "--num1", "1", "--num2", "3"]),
+        "1 / 3 = 0.3333333333333333"
+    );
+    assert_eq!(
+        plain(&["--type", "f32", "add", "0.1", "0.2"]),
+        "0.1 + 0.2 = 0.3"
+    );
+    assert_eq!(
+        plain(&["--type", "f64", "add", "0.1", "0.2"]),
+        "0.1 + 0.2 = 0.30000000000000004"
+    );
+    // Integer types divide to an integer.
+    assert_eq!(plain(&["div", "--num1", "7", "--num2", "2"]), "7 / 2 = 3");
+}
+
+#[test]
+fn names_the_type_in_json() {
+    for ty in ["i8", "u64", "f32", "big", "decimal", "rational"] {
+        let record = plain(&["--type", ty, "--format", "json", "add", "1", "2"]);
+        assert!(
+            record.contains(&format!(r#""type":"{}""#, ty)),
+            "{}",
+            record
+        );
+    }
+}
+
+#[test]
+fn refuses_unknown_types() {
+    let output = run(&["--type", "i7", "add", "1", "2"]);
+    assert_eq!(output.code, 1);
+    assert_eq!(output.stdout, "");
+    assert!(
+        output.stderr.starts_with(
+            "Error parsing option '--type' with value 'i7': unknown type `i7`, expected one of: i8,"
+        ),
+        "{}",
+        output.stderr
+    );
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use argh_demo::units::{Dimension, Quantity, Unit};
+use argh_demo::Operation;
+use common::Sandbox;
+
+fn quantity(text: &str) -> Quantity {
+    text.parse().unwrap()
+}
+
+fn combine(op: Operation, lhs: &str, rhs: &str) -> String {
+    quantity(lhs)
+        .combine(op, &quantity(rhs))
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn adds_temperatures_as_differences() {
+    assert_eq!(combine(Operation::Add, "10°C", "9°F"), "15 °C");
+    assert_eq!(combine(Operation::Sub, "10°C", "9°F"), "5 °C");
+    assert_eq!(combine(Operation::Add, "10C", "5C"), "15 °C");
+    assert_eq!(combine(Operation::Add, "300K", "1°C"), "301 K");
+    assert_eq!(combine(Operation::Add, "32°F", "5K"), "41 °F");
+}
+
+#[test]
+fn converts_temperatures_as_points() {
+    let unit = quantity("0C").unit;
+    assert_eq!(
+        quantity("-40°F").convert(unit).unwrap().to_string(),
+        "-40 °C"
+    );
+    assert_eq!(
+        quantity("212°F").convert(unit).unwrap().to_string(),
+        "100 °C"
+    );
+}
+
+#[test]
+fn refuses_mixed_dimensions() {
+    let err = quantity("1km")
+        .combine(Operation::Add, &quantity("1C"))
+        .unwrap_err();
+    assert_eq!(err.exit_code(), 4);
+}
+
+fn parse(text: &str) -> Result<Quantity, String> {
+    text.parse()
+}
+
+#[test]
+fn reads_compound_quantities_from_largest_unit() {
+    assert_eq!(quantity("3h15m").to_string(), "195 min");
+    assert_eq!(quantity("1d 2h 3m 4s").to_string(), "93784 s");
+    assert_eq!(quantity("1km5m").to_string(), "1005 m");
+    assert_eq!(quantity("-1h30min").to_string(), "-90 min");
+    for text in ["1h1h", "1m5km", "15m3h", "1s1ms1s"] {
+        let err = parse(text).unwrap_err();
+        assert!(
+            err.contains("largest unit to the smallest"),
+            "{}: {}",
+            text,
+            err
+        );
+    }
+    assert!(parse("1h5km").is_err());
+}
+
+#[test]
+fn reads_shared_names_by_their_neighbours() {
+    let time = Some(Dimension::Time);
+    assert_eq!(Quantity::parse_in("1m", time).unwrap().to_string(), "1 min");
+    assert_eq!(
+        Quantity::parse_in("1min", time).unwrap().to_string(),
+        "1 min"
+    );
+    assert_eq!(quantity("1m").to_string(), "1 m");
+    assert_eq!(Unit::find_in("m", time).unwrap().symbol(), "min");
+    assert_eq!(
+        Unit::find_in("m", Some(Dimension::Length))
+            .unwrap()
+            .symbol(),
+        "m"
+    );
+    assert_eq!(
+        Unit::find_in("s", Some(Dimension::Length))
+            .unwrap()
+            .symbol(),
+        "s"
+    );
+    assert_eq!(
+        Unit::find_in("parsec", time).unwrap_err(),
+        "unknown unit `parsec`"
+    );
+}
+
+#[test]
+fn adds_durations_as_written() {
+    let sandbox = Sandbox::new("units");
+    let add = |args: &[&str]| {
+        let mut all = vec!["--no-history", "add"];
+        all.extend_from_slice(args);
+        sandbox.stdout(&all).trim_end().to_string()
+    };
+    assert_eq!(add(&["3h15m", "47m"]), "3h15m + 47m = 14520 s");
+    assert_eq!(add(&["3h15m", "47m", "--human"]), "3h15m + 47m = 4h 2m");
+    assert_eq!(add(&["3h15m", "47min"]), "3h15m + 47min = 14520 s");
+    assert_eq!(add(&["1m", "1s"]), "1m + 1s = 61 s");
+    assert_eq!(add(&["1km", "5m"]), "1km + 5m = 1.005 km");
+    assert_eq!(add(&["1m", "30s", "--to", "m"]), "1m + 30s = 1.5 min");
+}
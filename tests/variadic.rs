@@ -0,0 +1,57 @@
+mod common;
+
+use common::Sandbox;
+
+fn plain(sandbox: &Sandbox, args: &[&str]) -> String {
+    let mut all = vec!["--no-history"];
+    all.extend_from_slice(args);
+    sandbox.stdout(&all).trim_end().to_string()
+}
+
+#[test]
+fn folds_any_number_of_operands() {
+    let sandbox = Sandbox::new("variadic");
+    assert_eq!(
+        plain(&sandbox, &["add", "1", "2", "3", "4"]),
+        "1 + 2 + 3 + 4 = 10"
+    );
+    assert_eq!(plain(&sandbox, &["sub", "10", "1", "2"]), "10 - 1 - 2 = 7");
+    assert_eq!(
+        plain(&sandbox, &["add", "--num1", "1", "--num2", "2"]),
+        "1 + 2 = 3"
+    );
+}
+
+#[test]
+fn orders_options_before_positionals_before_num() {
+    let sandbox = Sandbox::new("variadic");
+    // --num1 and --num2 come first, then the positionals, then every --num.
+    assert_eq!(
+        plain(
+            &sandbox,
+            &["sub", "--num", "-3", "1", "--num2", "2", "--num1", "20", "4", "--num", "5"]
+        ),
+        "20 - 2 - 1 - 4 - -3 - 5 = 11"
+    );
+    // Folding from the left, the order matters to subtraction.
+    assert_eq!(plain(&sandbox, &["sub", "1", "--num", "10"]), "1 - 10 = -9");
+}
+
+#[test]
+fn needs_at_least_two_operands() {
+    let sandbox = Sandbox::new("variadic");
+    for args in [&["add", "5"][..], &["sub", "--num", "5"], &["add", "5km"]] {
+        let mut all = vec!["--no-history"];
+        all.extend_from_slice(args);
+        let output = sandbox.run(&all);
+        assert_eq!(output.code, 2, "{:?}", args);
+        assert_eq!(
+            output.stderr,
+            format!("error: {} needs at least two numbers, got 1\n", args[0])
+        );
+    }
+    let output = sandbox.run_input(&[], &["--no-history", "add", "--stdin"], "5\n");
+    assert_eq!(output.code, 2);
+    let output = sandbox.run(&["--no-history", "add"]);
+    assert_eq!(output.stderr, "error: no operands given\n");
+}